@@ -0,0 +1,143 @@
+use anyhow::Result;
+
+use crate::{tags, Frame, GetItem, Item};
+
+/// Battery unit of the device
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct BatteryDescriptor {
+    /// index of the battery, used to request the battery data
+    pub index: u32,
+
+    /// number of DCBs of the battery
+    pub dcb_count: u32,
+
+    /// specified capacity in watt hours
+    pub specified_capacity: Option<u32>,
+
+    /// specified discharge power in watt
+    pub specified_discharge_power: Option<u32>,
+
+    /// specified charge power in watt
+    pub specified_charge_power: Option<u32>,
+
+    /// specified maximum number of DCBs
+    pub specified_max_dcb_count: Option<u32>,
+}
+
+/// Returns frame requesting the available batteries
+pub fn request_available_batteries() -> Frame {
+    let mut frame = Frame::new();
+    frame.push_item(Item::from_tag(tags::BAT::REQ_AVAILABLE_BATTERIES));
+    frame
+}
+
+/// Returns the batteries of the `AVAILABLE_BATTERIES` container in the frame
+///
+/// # Arguments
+///
+/// * `frame` - the response frame
+///
+/// # Examples
+///
+/// ```no_run
+/// use rscp::bat;
+/// let mut c = rscp::Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+/// c.connect("energy.storage.local", None).unwrap();
+/// let result_frame = c.send_receive_frame(&bat::request_available_batteries()).unwrap();
+/// for battery in bat::parse_available_batteries(&result_frame).unwrap() {
+///     println!("battery {} with {} DCBs", battery.index, battery.dcb_count);
+/// }
+/// ```
+pub fn parse_available_batteries(frame: &Frame) -> Result<Vec<BatteryDescriptor>> {
+    let mut batteries = Vec::new();
+    for descriptor in frame.get_item(tags::BAT::AVAILABLE_BATTERIES.into())?.as_container()? {
+        if descriptor.tag == tags::BAT::INSTANCE_DESCRIPTOR as u32 {
+            batteries.push(parse_descriptor(descriptor)?);
+        }
+    }
+    Ok(batteries)
+}
+
+/// Returns battery parsed from `INSTANCE_DESCRIPTOR` container
+///
+/// # Arguments
+///
+/// * `descriptor` - the instance descriptor container item
+fn parse_descriptor(descriptor: &Item) -> Result<BatteryDescriptor> {
+    let mut battery = BatteryDescriptor {
+        index: as_unsigned(descriptor.get_item(tags::BAT::INDEX.into())?)?,
+        dcb_count: as_unsigned(descriptor.get_item(tags::BAT::DCB_COUNT.into())?)?,
+        specified_capacity: None,
+        specified_discharge_power: None,
+        specified_charge_power: None,
+        specified_max_dcb_count: None,
+    };
+    if let Some(spec) = descriptor.as_container()?.iter().find(|item| item.tag == tags::BAT::BATTERY_SPEC as u32) {
+        for item in spec.as_container()? {
+            match tags::BAT::from(item.tag) {
+                tags::BAT::SPECIFIED_CAPACITY => battery.specified_capacity = Some(as_unsigned(item)?),
+                tags::BAT::SPECIFIED_DSCHARGE_POWER => battery.specified_discharge_power = Some(as_unsigned(item)?),
+                tags::BAT::SPECIFIED_CHARGE_POWER => battery.specified_charge_power = Some(as_unsigned(item)?),
+                tags::BAT::SPECIFIED_MAX_DCB_COUNT => battery.specified_max_dcb_count = Some(as_unsigned(item)?),
+                _ => (),
+            }
+        }
+    }
+    Ok(battery)
+}
+
+/// Returns the data of unsigned integer items, the firmware uses different sizes
+///
+/// # Arguments
+///
+/// * `item` - the item
+fn as_unsigned(item: &Item) -> Result<u32> {
+    item.as_u8().map(u32::from).or_else(|_| item.as_u16().map(u32::from)).or_else(|_| item.as_u32())
+}
+
+/// ################################################
+///      TEST TEST TEST
+/// ################################################
+
+#[test]
+fn test_parse_available_batteries() {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::BAT::AVAILABLE_BATTERIES, vec![
+        Item::new_tag(tags::BAT::INSTANCE_DESCRIPTOR, vec![
+            Item::new_tag(tags::BAT::INDEX, 0u16),
+            Item::new_tag(tags::BAT::DCB_COUNT, 3u8),
+            Item::new_tag(tags::BAT::BATTERY_SPEC, vec![
+                Item::new_tag(tags::BAT::SPECIFIED_CAPACITY, 13800u32),
+                Item::new_tag(tags::BAT::SPECIFIED_DSCHARGE_POWER, 9000u32),
+                Item::new_tag(tags::BAT::SPECIFIED_CHARGE_POWER, 9000u32),
+                Item::new_tag(tags::BAT::SPECIFIED_MAX_DCB_COUNT, 6u8),
+            ]),
+        ]),
+        Item::new_tag(tags::BAT::INSTANCE_DESCRIPTOR, vec![
+            Item::new_tag(tags::BAT::INDEX, 1u16),
+            Item::new_tag(tags::BAT::DCB_COUNT, 2u8),
+        ]),
+    ]));
+
+    let batteries = parse_available_batteries(&frame).unwrap();
+    assert_eq!(batteries, vec![
+        BatteryDescriptor {
+            index: 0,
+            dcb_count: 3,
+            specified_capacity: Some(13800),
+            specified_discharge_power: Some(9000),
+            specified_charge_power: Some(9000),
+            specified_max_dcb_count: Some(6),
+        },
+        BatteryDescriptor {
+            index: 1,
+            dcb_count: 2,
+            specified_capacity: None,
+            specified_discharge_power: None,
+            specified_charge_power: None,
+            specified_max_dcb_count: None,
+        },
+    ]);
+
+    assert!(parse_available_batteries(&Frame::new()).is_err());
+}
@@ -1,15 +1,40 @@
 use anyhow::{bail, Result};
-use log::{debug, info};
+use log::{debug, info, log_enabled, warn, Level};
 use std::io::{Read, Write};
+#[cfg(feature = "proxy")]
+use std::net::SocketAddr;
 use std::net::{Shutdown, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use crate::encryption::{RscpEncryption, BLOCK_SIZE};
-use crate::{tags, Errors, Frame, GetItem, Item, UserLevel};
+use crate::frame::{frame_size, FrameReader};
+use crate::info::DeviceInfo;
+use crate::{tags, trace_frame, Errors, Frame, FrozenFrame, GetItem, Item, UserLevel};
 
 /// default RSCP Port
 const DEFAULT_PORT: u16 = 5033;
 
+/// default maximum total duration of reading a response
+const DEFAULT_READ_BUDGET: Duration = Duration::from_secs(10);
+
+/// number of read timeouts to wait for the rest of a partially received block
+const MAX_UNALIGNED_TIMEOUTS: usize = 10;
+
+/// log target of connection logging
+const LOG_TARGET: &str = "rscp::client";
+
+/// Information of established connection
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct ConnectInfo {
+    /// level of the authenticated user
+    pub user_level: UserLevel,
+
+    /// protocol version used for the connection
+    pub protocol_version: u8,
+}
+
 /// RSCP Client object
 pub struct Client {
     /// Connection status
@@ -26,6 +51,34 @@ pub struct Client {
 
     /// password for connection
     password: String,
+
+    /// size of chunks read from the connection
+    read_chunk_size: usize,
+
+    /// token to cancel running reads
+    cancel_token: Option<Arc<AtomicBool>>,
+
+    /// received and decrypted data of not yet returned frames
+    pending: Vec<u8>,
+
+    /// maximum total duration of reading a response
+    read_budget: Option<Duration>,
+
+    /// decrypted data of the last received frame
+    last_response: Vec<u8>,
+
+    /// host and port of the last connect, used to reconnect on retries
+    address: Option<(String, u16)>,
+
+    /// number of times a failed request is resent on a new connection
+    retries: usize,
+
+    /// write requests are resent on retries too
+    retry_writes: bool,
+
+    /// SOCKS5 proxy used to connect
+    #[cfg(feature = "proxy")]
+    proxy: Option<SocketAddr>,
 }
 
 impl Client {
@@ -50,10 +103,139 @@ impl Client {
             enc_processor: RscpEncryption::new(rscp_key),
             username: username,
             password,
+            read_chunk_size: BLOCK_SIZE,
+            cancel_token: None,
+            pending: Vec::new(),
+            read_budget: Some(DEFAULT_READ_BUDGET),
+            last_response: Vec::new(),
+            address: None,
+            retries: 0,
+            retry_writes: false,
+            #[cfg(feature = "proxy")]
+            proxy: None,
         }
     }
 
-    /// Connects to given host
+    /// Sets the SOCKS5 proxy used by the following connects, `None` connects directly
+    ///
+    /// The host name passed to `connect` is resolved by the proxy.
+    ///
+    /// # Arguments
+    ///
+    /// * `proxy` - address of the SOCKS5 proxy
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rscp;
+    /// let mut c = rscp::Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    /// c.set_proxy(Some("127.0.0.1:1080".parse().unwrap()));
+    /// c.connect("energy.storage.local", None).unwrap();
+    /// ```
+    #[cfg(feature = "proxy")]
+    pub fn set_proxy(&mut self, proxy: Option<SocketAddr>) {
+        self.proxy = proxy;
+    }
+
+    /// Sets a token to cancel a running request
+    ///
+    /// The token is checked between reads from the connection, if it is set the request is aborted
+    /// with `Errors::Cancelled`. The token is not reset by the client.
+    ///
+    /// The rest of a cancelled response is left on the connection and breaks the decryption of the
+    /// following responses, so the client drops the connection on cancel and `connect` must be
+    /// called again.
+    ///
+    /// # Arguments
+    ///
+    /// * `cancel_token` - the cancellation token
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use std::sync::atomic::AtomicBool;
+    /// use rscp;
+    /// let mut c = rscp::Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    /// let cancel_token = Arc::new(AtomicBool::new(false));
+    /// c.set_cancel_token(cancel_token.clone());
+    /// ```
+    pub fn set_cancel_token(&mut self, cancel_token: Arc<AtomicBool>) {
+        self.cancel_token = Some(cancel_token);
+    }
+
+    /// Sets the maximum total duration of reading a response, the default is 10 seconds
+    ///
+    /// Reading ends when the connection is idle for the read timeout of 500ms. A peer trickling
+    /// data slower than that keeps the read running, the budget aborts it with `Errors::Receive`.
+    ///
+    /// # Arguments
+    ///
+    /// * `budget` - maximum duration of reading a response
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use rscp;
+    /// let mut c = rscp::Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    /// c.set_read_budget(Duration::from_secs(10));
+    /// ```
+    pub fn set_read_budget(&mut self, budget: Duration) {
+        self.read_budget = Some(budget);
+    }
+
+    /// Sets the number of times `send_receive_frame` reconnects and resends a request after the
+    /// connection failed, the default is 0
+    ///
+    /// Write requests are not resent unless enabled by `set_retry_writes`, as the device may have
+    /// applied them before the connection failed, see `Frame::is_write`. Cancelled requests are
+    /// not resent.
+    ///
+    /// # Arguments
+    ///
+    /// * `retries` - number of retries
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp;
+    /// let mut c = rscp::Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    /// c.set_retries(2);
+    /// ```
+    pub fn set_retries(&mut self, retries: usize) {
+        self.retries = retries;
+    }
+
+    /// Sets whether write requests are resent on retries too, the default is false
+    ///
+    /// # Arguments
+    ///
+    /// * `retry_writes` - resend write requests
+    pub fn set_retry_writes(&mut self, retry_writes: bool) {
+        self.retry_writes = retry_writes;
+    }
+
+    /// Sets the size of chunks read from the connection, rounded up to a multiple of the encryption block size
+    ///
+    /// Larger chunks reduce the number of reads for big frames like history data.
+    ///
+    /// # Arguments
+    ///
+    /// * `chunk_size` - size of read chunks in bytes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp;
+    /// let mut c = rscp::Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    /// c.set_read_chunk_size(4096);
+    /// ```
+    pub fn set_read_chunk_size(&mut self, chunk_size: usize) {
+        self.read_chunk_size = chunk_size.max(1).div_ceil(BLOCK_SIZE) * BLOCK_SIZE;
+    }
+
+    /// Connects to given host and returns the connection information
     ///
     /// # Arguments
     ///
@@ -72,49 +254,146 @@ impl Client {
     ///     }
     /// }
     /// ```
-    pub fn connect(&mut self, host: &str, port: Option<u16>) -> Result<()> {
+    pub fn connect(&mut self, host: &str, port: Option<u16>) -> Result<ConnectInfo> {
         let host_port = port.unwrap_or(DEFAULT_PORT);
-        let addr_list = format!("{}:{}", host, host_port).to_socket_addrs()?;
-        let addr = addr_list.last().unwrap();
-        info!("Connect to {}:{}", host, host_port);
+        info!(target: LOG_TARGET, "Connect to {}:{}", host, host_port);
 
-        let stream = TcpStream::connect(addr)?;
-        stream.set_read_timeout(Some(std::time::Duration::from_millis(500)))?;
+        let stream = self.open_stream(host, host_port)?;
+        stream.set_read_timeout(Some(Duration::from_millis(500)))?;
+        self.address = Some((host.to_string(), host_port));
         self.connected = true;
         self.connection = Some(Arc::new(Mutex::new(stream)));
-        info!("Connected");
+        self.pending.clear();
+        // the ivs chain the messages of a single connection
+        self.enc_processor.reset_ivs();
+        info!(target: LOG_TARGET, "Connected");
 
-        let mut frame = Frame::new();
-        frame.push_item(Item::new(tags::RSCP::AUTHENTICATION.into(), vec![
-            Item::new(tags::RSCP::AUTHENTICATION_USER.into(), self.username.to_string()),
-            Item::new(tags::RSCP::AUTHENTICATION_PASSWORD.into(), self.password.to_string()),
-        ]));
+        self.auth_exchange()
+    }
+
+    /// Opens the connection to the host, through the proxy if configured
+    fn open_stream(&self, host: &str, port: u16) -> Result<TcpStream> {
+        #[cfg(feature = "proxy")]
+        if let Some(proxy) = self.proxy {
+            info!(target: LOG_TARGET, "Connect through proxy {}", proxy);
+            return crate::proxy::connect(proxy, host, port);
+        }
+
+        let addr_list = format!("{}:{}", host, port).to_socket_addrs()?;
+        let addr = addr_list.last().unwrap();
+        Ok(TcpStream::connect(addr)?)
+    }
 
-        info!("Authenticate");
+    /// Authenticates the established connection with the current credentials and returns the user level
+    ///
+    /// Called by `connect`, use it to authenticate again e.g. after changing the credentials. The
+    /// connection is closed if the authentication fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rscp;
+    /// let mut c = rscp::Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    /// c.connect("energy.storage.local", None).unwrap();
+    /// c.set_credentials("OTHER_USER".to_string(), "OTHER_PASSWORD".to_string());
+    /// let user_level = c.authenticate().unwrap();
+    /// ```
+    pub fn authenticate(&mut self) -> Result<UserLevel> {
+        if !self.connected {
+            bail!(Errors::NotConnected)
+        }
+        Ok(self.auth_exchange()?.user_level)
+    }
+
+    /// Sends the authentication frame and returns the connection information
+    fn auth_exchange(&mut self) -> Result<ConnectInfo> {
+        let frame = self.auth_frame();
+
+        info!(target: LOG_TARGET, "Authenticate");
         match self.send_receive_frame(&frame) {
             Ok(result_frame) => {
                 let user_level = result_frame.get_item_data::<u8>(tags::RSCP::AUTHENTICATION.into()).unwrap();
                 let user_level_type = UserLevel::from(user_level.clone());
-                info!("Authenticated as {:?}", user_level_type);
+                info!(target: LOG_TARGET, "Authenticated as {:?}", user_level_type);
+                Ok(ConnectInfo {
+                    user_level: user_level_type,
+                    protocol_version: result_frame.protocol_version(),
+                })
             }
-            Err(_) => {
+            Err(err) => {
                 self.disconnect()?;
+                // a wrong key decrypts the response to garbage without valid frame header
+                if let Some(Errors::InvalidMagic { .. } | Errors::ChecksumMismatch { .. }) = err.downcast_ref::<Errors>() {
+                    bail!(Errors::KeyInvalid)
+                }
                 bail!(Errors::AuthFailed)
             }
         }
+    }
 
-        Ok(())
+    /// Sets the credentials used to authenticate on the next connect or authenticate
+    ///
+    /// # Arguments
+    ///
+    /// * `username` - RSCP username
+    /// * `password` - RSCP password
+    pub fn set_credentials(&mut self, username: String, password: String) {
+        self.username = username;
+        self.password = password;
+    }
+
+    /// Returns the authentication frame with the current credentials
+    fn auth_frame(&self) -> Frame {
+        let mut frame = Frame::new();
+        frame.push_item(Item::new_tag(tags::RSCP::AUTHENTICATION, vec![
+            Item::new_tag(tags::RSCP::AUTHENTICATION_USER, self.username.to_string()),
+            Item::new_tag(tags::RSCP::AUTHENTICATION_PASSWORD, self.password.to_string()),
+        ]));
+        frame
     }
 
     /// Disconnects from host
     pub fn disconnect(&mut self) -> Result<()> {
         self.connected = false;
-        self.connection.as_mut().unwrap().as_ref().lock().unwrap().shutdown(Shutdown::Both)?;
+        self.pending.clear();
+        if let Some(connection) = self.connection.take() {
+            connection.lock().unwrap().shutdown(Shutdown::Both)?;
+        }
+        Ok(())
+    }
+
+    /// Flushes pending writes of the connection
+    pub fn flush(&mut self) -> Result<()> {
+        if !self.connected {
+            bail!(Errors::NotConnected)
+        }
+        self.connection.as_ref().unwrap().lock().unwrap().flush()?;
+        Ok(())
+    }
+
+    /// Shuts down the read, write, or both halves of the connection
+    ///
+    /// The client is no longer connected if the write half is shut down.
+    ///
+    /// # Arguments
+    ///
+    /// * `how` - the halves to shut down
+    pub fn shutdown(&mut self, how: Shutdown) -> Result<()> {
+        if !self.connected {
+            bail!(Errors::NotConnected)
+        }
+        if how != Shutdown::Read {
+            self.connected = false;
+        }
+        self.connection.as_ref().unwrap().lock().unwrap().shutdown(how)?;
         Ok(())
     }
 
     /// Sends and receives frame from connection
     ///
+    /// Buffered responses not yet returned by `receive` are discarded, use `send` and `receive` to
+    /// pipeline requests.
+    ///
     /// # Arguments
     ///
     /// * `frame` - frame to send
@@ -145,24 +424,289 @@ impl Client {
     /// }
     /// ```
     pub fn send_receive_frame(&mut self, frame: &Frame) -> Result<Frame> {
-        debug!("<< {:?}", frame);
+        let mut retries = 0;
+        loop {
+            let result = self.send_receive_once(frame);
+            match result {
+                Err(err) if retries < self.retries && is_transient(&err) && (self.retry_writes || !frame.is_write()) => {
+                    let Some((host, port)) = self.address.clone() else {
+                        return Err(err);
+                    };
+                    retries += 1;
+                    warn!(target: LOG_TARGET, "Request failed: {}, reconnect and retry {}/{}", err, retries, self.retries);
+                    // the connection is replaced, errors of the broken connection don't matter
+                    let _ = self.disconnect();
+                    self.connect(&host, Some(port))?;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Sends the frame and receives the response once
+    fn send_receive_once(&mut self, frame: &Frame) -> Result<Frame> {
+        // buffered responses of previous requests are stale
+        self.pending.clear();
+        self.send(frame)?;
+        let result = self.receive();
+        self.pending.clear();
+        result
+    }
+
+    /// Requests the identity of the device
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// let mut c = rscp::Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    /// c.connect("energy.storage.local", None).unwrap();
+    /// let info = c.device_info().unwrap();
+    /// println!("{} running {}", info.serial_number, info.sw_release);
+    /// ```
+    pub fn device_info(&mut self) -> Result<DeviceInfo> {
+        let result_frame = self.send_receive_frame(&DeviceInfo::request())?;
+        DeviceInfo::from_frame(&result_frame)
+    }
+
+    /// Requests the device time and returns the measured round-trip time
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// let mut c = rscp::Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    /// c.connect("energy.storage.local", None).unwrap();
+    /// println!("latency: {:?}", c.ping().unwrap());
+    /// ```
+    pub fn ping(&mut self) -> Result<Duration> {
+        let mut frame = Frame::new();
+        frame.push_item(Item::from_tag(tags::INFO::UTC_TIME));
+        let start = Instant::now();
+        self.send_receive_frame(&frame)?;
+        Ok(start.elapsed())
+    }
+
+    /// Sends frame to the connection without waiting for the response
+    ///
+    /// Multiple frames can be sent before receiving the responses using `receive`, the responses
+    /// are returned in the order of the requests.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - frame to send
+    pub fn send(&mut self, frame: &Frame) -> Result<()> {
+        debug!(target: LOG_TARGET, "<< {:?}", frame);
+        if log_enabled!(target: crate::LOG_TARGET, Level::Trace) {
+            trace_frame(frame);
+        }
         let data = frame.to_bytes()?;
-        // debug!("<< Frame: {:02x?}", data);
+        self.send_bytes(data)
+    }
+
+    /// Sends a pre-serialized frame to the connection without waiting for the response
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - frozen frame to send
+    pub fn send_frozen(&mut self, frame: &FrozenFrame) -> Result<()> {
+        debug!(target: LOG_TARGET, "<< {:?}", frame.frame());
+        if log_enabled!(target: crate::LOG_TARGET, Level::Trace) {
+            trace_frame(frame.frame());
+        }
+        self.send_bytes(frame.as_bytes().to_vec())
+    }
+
+    /// Sends multiple frames to the connection with a single write without waiting for the responses
+    ///
+    /// The frames are encrypted in order, so the data on the connection is identical to sending
+    /// the frames one by one using `send`. The responses are returned by `receive` in the order of
+    /// the frames.
+    ///
+    /// # Arguments
+    ///
+    /// * `frames` - frames to send
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rscp::{tags, Frame, Item};
+    /// let mut c = rscp::Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    /// c.connect("energy.storage.local", None).unwrap();
+    /// let frames = [Frame::request_tags(&[tags::EMS::POWER_PV.into()]), Frame::request_tags(&[tags::EMS::BAT_SOC.into()])];
+    /// c.send_batch(&frames).unwrap();
+    /// for _ in 0..frames.len() {
+    ///     println!("{:?}", c.receive().unwrap());
+    /// }
+    /// ```
+    pub fn send_batch(&mut self, frames: &[Frame]) -> Result<()> {
+        // serialize all frames first, a failing frame must not advance the encryption state
+        let mut data = Vec::with_capacity(frames.len());
+        for frame in frames {
+            debug!(target: LOG_TARGET, "<< {:?}", frame);
+            if log_enabled!(target: crate::LOG_TARGET, Level::Trace) {
+                trace_frame(frame);
+            }
+            data.push(frame.to_bytes()?);
+        }
+        let mut enc_data = Vec::new();
+        for frame_data in data {
+            enc_data.extend(self.enc_processor.encrypt(frame_data)?);
+        }
+        self.write_to_stream(&enc_data)
+    }
+
+    /// Encrypts and writes the serialized frame to the connection
+    fn send_bytes(&mut self, data: Vec<u8>) -> Result<()> {
+        // debug!(target: LOG_TARGET, "<< Frame: {:02x?}", data);
         let enc_data = self.enc_processor.encrypt(data)?;
 
         self.write_to_stream(&enc_data)?;
-        let return_enc_data = self.read_from_stream()?;
-        if return_enc_data.len() == 0 {
-            bail!(Errors::ReceiveNothing)
+        Ok(())
+    }
+
+    /// Receives the next frame from the connection
+    ///
+    /// The device may coalesce multiple responses into a single transmission, these are buffered
+    /// and returned by the following calls.
+    pub fn receive(&mut self) -> Result<Frame> {
+        // keep the data to be able to capture a failing payload, see `last_response_bytes`
+        self.last_response = self.receive_bytes()?;
+        let result_frame = Frame::from_slice(&self.last_response)?;
+        debug!(target: LOG_TARGET, ">> {:?}", result_frame);
+        if log_enabled!(target: crate::LOG_TARGET, Level::Trace) {
+            trace_frame(&result_frame);
         }
 
-        let return_data = self.enc_processor.decrypt(return_enc_data)?;
-        // debug!(">> Frame: {:02x?}", return_data);
+        Ok(result_frame)
+    }
 
-        let result_frame = Frame::from_bytes(return_data)?;
-        debug!(">> {:?}", result_frame);
+    /// Sends frame and passes each top-level item of the response to the callback
+    ///
+    /// The response is decrypted and parsed while it is read, each top-level item is passed on as
+    /// soon as it is complete. Only the current item is held in memory, the response is not kept
+    /// for `last_response_bytes`.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - frame to send
+    /// * `on_item` - callback for each top-level item of the response
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rscp::{tags, Frame, Item};
+    /// let mut c = rscp::Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    /// c.connect("energy.storage.local", None).unwrap();
+    /// let mut frame = Frame::new();
+    /// frame.push_item(Item { tag: tags::INFO::SERIAL_NUMBER.into(), data: None });
+    /// c.request_streaming(&frame, |item| println!("{:?}", item)).unwrap();
+    /// ```
+    pub fn request_streaming(&mut self, frame: &Frame, mut on_item: impl FnMut(&Item)) -> Result<()> {
+        // buffered responses of previous requests are stale
+        self.pending.clear();
+        self.last_response.clear();
+        self.send(frame)?;
+        let result = self.receive_streaming(&mut on_item);
+        self.pending.clear();
+        result
+    }
 
-        Ok(result_frame)
+    /// Reads the next frame block by block and passes each top-level item to the callback
+    fn receive_streaming(&mut self, on_item: &mut impl FnMut(&Item)) -> Result<()> {
+        let mut reader = FrameReader::new();
+        let mut complete = false;
+        let mut received = 0;
+        while !complete {
+            let len = self.read_from_stream_with(|enc_processor, blocks| {
+                let data = enc_processor.decrypt(blocks.to_vec())?;
+                complete = reader.push(&data, |item| {
+                    debug!(target: LOG_TARGET, ">> {:?}", item);
+                    on_item(&item);
+                })?;
+                Ok(complete)
+            })?;
+            if len == 0 && !complete {
+                if received == 0 {
+                    bail!(Errors::ReceiveNothing)
+                }
+                bail!(Errors::Receive(format!("incomplete frame, received {} bytes", received)))
+            }
+            received += len;
+        }
+        Ok(())
+    }
+
+    /// Receives the decrypted data of the next frame
+    fn receive_bytes(&mut self) -> Result<Vec<u8>> {
+        // a frame can be split over multiple reads, read until it is complete
+        while !self.pending_frame_complete() {
+            if let Err(err) = self.read_pending() {
+                self.pending.clear();
+                return Err(err);
+            }
+        }
+
+        // split off the first frame including the zero padding of the encryption
+        let frame_size = frame_size(&self.pending).map_or(self.pending.len(), |size| size.div_ceil(BLOCK_SIZE) * BLOCK_SIZE);
+        let remaining = self.pending.split_off(frame_size.min(self.pending.len()));
+        let return_data = std::mem::replace(&mut self.pending, remaining);
+        if self.pending.iter().all(|byte| *byte == 0) {
+            self.pending.clear();
+        }
+        // debug!(target: LOG_TARGET, ">> Frame: {:02x?}", return_data);
+        Ok(return_data)
+    }
+
+    /// Returns true if the pending data starts with a complete frame, data without frame header is
+    /// passed to the parser to report the error
+    fn pending_frame_complete(&self) -> bool {
+        frame_size(&self.pending).map_or(!self.pending.is_empty(), |size| size <= self.pending.len())
+    }
+
+    /// Reads and decrypts the next data from the connection and appends it to the pending data
+    fn read_pending(&mut self) -> Result<()> {
+        let return_enc_data = self.read_from_stream()?;
+        if return_enc_data.is_empty() {
+            if self.pending.is_empty() {
+                bail!(Errors::ReceiveNothing)
+            }
+            bail!(Errors::Receive(format!("incomplete frame, received {} bytes", self.pending.len())))
+        }
+        let data = self.enc_processor.decrypt(return_enc_data)?;
+        self.pending.extend(data);
+        Ok(())
+    }
+
+    /// Returns the decrypted data of the last received frame including the encryption padding
+    ///
+    /// The data is kept if parsing the frame fails, to be able to capture the failing payload.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rscp::{tags, Frame};
+    /// let mut c = rscp::Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    /// c.connect("energy.storage.local", None).unwrap();
+    /// let frame = Frame::request_tags(&[tags::INFO::SERIAL_NUMBER.into()]);
+    /// if let Err(err) = c.send_receive_frame(&frame) {
+    ///     println!("{}\n{}", err, rscp::hexdump(c.last_response_bytes()));
+    /// }
+    /// ```
+    pub fn last_response_bytes(&self) -> &[u8] {
+        &self.last_response
+    }
+
+    /// Sends raw bytes to the connection and returns the raw response bytes
+    ///
+    /// This is an escape hatch for protocol research and replay of captured data. The bytes are
+    /// neither framed nor encrypted, and the response is not decrypted. As the encryption state of
+    /// the client is not updated, sending raw data usually breaks the following encrypted frames.
+    ///
+    /// # Arguments
+    ///
+    /// * `bytes` - raw data to send
+    pub fn send_raw(&mut self, bytes: &[u8]) -> Result<Vec<u8>> {
+        self.write_to_stream(bytes)?;
+        self.read_from_stream()
     }
 
     /// writes data to stream
@@ -174,31 +718,796 @@ impl Client {
         if !self.connected {
             bail!(Errors::NotConnected)
         }
-        self.connection.as_mut().unwrap().as_ref().lock().unwrap().write(&data)?;
+        let connection = self.connection.as_ref().unwrap();
+        let mut stream = connection.lock().unwrap();
+        stream.write_all(data)?;
+        stream.flush()?;
         Ok(())
     }
 
     /// reads data from stream
     fn read_from_stream(&mut self) -> Result<Vec<u8>> {
+        let mut data = Vec::new();
+        self.read_from_stream_with(|_, blocks| {
+            data.extend_from_slice(blocks);
+            Ok(false)
+        })?;
+        Ok(data)
+    }
+
+    /// reads data from stream and passes the encryption and the block aligned data of each read to
+    /// the callback, returns the number of bytes read
+    ///
+    /// # Arguments
+    ///
+    /// * `on_blocks` - callback for the data of each read, returns true to stop reading
+    fn read_from_stream_with<F: FnMut(&mut RscpEncryption, &[u8]) -> Result<bool>>(&mut self, mut on_blocks: F) -> Result<usize> {
         if !self.connected {
             bail!(Errors::NotConnected)
         }
-        let mut buffer = [0 as u8; BLOCK_SIZE];
-        let mut data: Vec<u8> = Vec::new();
-        loop {
-            match self.connection.as_mut().unwrap().as_ref().lock().unwrap().read_exact(&mut buffer) {
-                Ok(_) => {
-                    data.extend_from_slice(&buffer);
+        let result = {
+            let connection = self.connection.as_ref().unwrap();
+            let mut stream = connection.lock().unwrap();
+            let enc_processor = &mut self.enc_processor;
+            read_blocks_with(&mut *stream, self.read_chunk_size, self.cancel_token.as_deref(), self.read_budget, |blocks| on_blocks(enc_processor, blocks))
+        };
+        // the rest of the cancelled response can not be decrypted anymore, drop the connection
+        if matches!(&result, Err(err) if matches!(err.downcast_ref::<Errors>(), Some(Errors::Cancelled))) {
+            info!(target: LOG_TARGET, "Cancelled, connection dropped");
+            self.connected = false;
+            self.connection = None;
+            self.pending.clear();
+        }
+        result
+    }
+}
+
+/// returns true if the error is caused by the connection and the request can be resent on a new connection
+///
+/// # Arguments
+///
+/// * `err` - the error of the request
+fn is_transient(err: &anyhow::Error) -> bool {
+    err.downcast_ref::<std::io::Error>().is_some() || matches!(err.downcast_ref::<Errors>(), Some(Errors::Receive(_) | Errors::ReceiveNothing | Errors::NotConnected))
+}
+
+/// reads encrypted blocks from reader until the read timeout is hit and passes the complete blocks
+/// of each read to the callback, returns the number of bytes read
+///
+/// If the data is not aligned to the block size, the read is retried for the rest of the block up
+/// to `MAX_UNALIGNED_TIMEOUTS` times. A connection closed in the middle of a block is an error.
+///
+/// # Arguments
+///
+/// * `reader` - the reader
+/// * `chunk_size` - maximum number of bytes per read
+/// * `cancel_token` - optional token to abort reading
+/// * `budget` - optional maximum total duration of reading
+/// * `on_blocks` - callback for the complete blocks of each read, returns true to stop reading
+fn read_blocks_with<R: Read, F: FnMut(&[u8]) -> Result<bool>>(reader: &mut R, chunk_size: usize, cancel_token: Option<&AtomicBool>, budget: Option<Duration>, mut on_blocks: F) -> Result<usize> {
+    let mut buffer = vec![0u8; chunk_size];
+    // data of an incomplete block, kept until the rest of the block is read
+    let mut partial: Vec<u8> = Vec::new();
+    let mut len_total = 0;
+    let start = Instant::now();
+    let mut unaligned_timeouts = 0;
+    loop {
+        if cancel_token.is_some_and(|token| token.load(Ordering::Relaxed)) {
+            bail!(Errors::Cancelled)
+        }
+        if let Some(budget) = budget.filter(|budget| start.elapsed() > *budget) {
+            bail!(Errors::Receive(format!("read budget of {:?} exceeded after {} bytes", budget, len_total)))
+        }
+        match reader.read(&mut buffer) {
+            Ok(0) => {
+                if !partial.is_empty() {
+                    bail!(Errors::Receive("connection closed mid-block".to_string()))
                 }
-                Err(ref e) if e.kind() == std::io::ErrorKind::TimedOut => {
-                    break;
+                break;
+            }
+            Ok(len) => {
+                len_total += len;
+                partial.extend_from_slice(&buffer[..len]);
+                let aligned = partial.len() / BLOCK_SIZE * BLOCK_SIZE;
+                if aligned > 0 {
+                    let blocks: Vec<u8> = partial.drain(..aligned).collect();
+                    if on_blocks(&blocks)? {
+                        break;
+                    }
                 }
-                Err(_) => {
+            }
+            // read timeout is reported as WouldBlock on unix and TimedOut on windows,
+            // wait for the rest of a block if the data is not aligned for decryption
+            Err(ref e) if e.kind() == std::io::ErrorKind::TimedOut || e.kind() == std::io::ErrorKind::WouldBlock => {
+                if partial.is_empty() {
                     break;
-                    //return Err(anyhow!("error receiving data: {}", e))
                 }
+                unaligned_timeouts += 1;
+                if unaligned_timeouts >= MAX_UNALIGNED_TIMEOUTS {
+                    bail!(Errors::Receive(format!("timeout waiting for the rest of a block after {} bytes", len_total)))
+                }
+            }
+            Err(e) => {
+                bail!(Errors::Receive(e.to_string()))
             }
         }
-        Ok(data)
+    }
+    Ok(len_total)
+}
+
+/// ################################################
+///      TEST TEST TEST
+/// ################################################
+
+#[test]
+fn test_read_blocks() {
+    let mut reader = TestReader { chunks: vec![Ok(vec![0x01; 16]), Ok(vec![0x02; 16]), Ok(vec![0x03; BLOCK_SIZE])] };
+    let data = read_blocks(&mut reader, BLOCK_SIZE, None, None).unwrap();
+    assert_eq!(data.len(), 2 * BLOCK_SIZE);
+    assert_eq!(data[..16], [0x01; 16]);
+    assert_eq!(data[BLOCK_SIZE..], [0x03; BLOCK_SIZE]);
+}
+
+/// reads encrypted blocks from reader until the read timeout is hit and returns the data
+#[cfg(test)]
+fn read_blocks<R: Read>(reader: &mut R, chunk_size: usize, cancel_token: Option<&AtomicBool>, budget: Option<Duration>) -> Result<Vec<u8>> {
+    let mut data: Vec<u8> = Vec::new();
+    read_blocks_with(reader, chunk_size, cancel_token, budget, |blocks| {
+        data.extend_from_slice(blocks);
+        Ok(false)
+    })?;
+    Ok(data)
+}
+
+#[cfg(test)]
+struct TestReader {
+    chunks: Vec<std::io::Result<Vec<u8>>>,
+}
+
+#[cfg(test)]
+impl Read for TestReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.chunks.is_empty() {
+            return Err(std::io::Error::from(std::io::ErrorKind::WouldBlock));
+        }
+        match self.chunks.remove(0) {
+            Ok(mut chunk) => {
+                let len = chunk.len().min(buf.len());
+                buf[..len].copy_from_slice(&chunk[..len]);
+                if len < chunk.len() {
+                    self.chunks.insert(0, Ok(chunk.split_off(len)));
+                }
+                Ok(len)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[test]
+fn test_read_blocks_budget() {
+    // the reader trickles a byte every 10ms and never hits the read timeout
+    let mut reader = SlowReader { delay: Duration::from_millis(10) };
+    let start = Instant::now();
+    let err = read_blocks(&mut reader, BLOCK_SIZE, None, Some(Duration::from_millis(100))).unwrap_err();
+    assert!(format!("{}", err).starts_with("Receive error: read budget of 100ms exceeded after "));
+    assert!(start.elapsed() < Duration::from_secs(1));
+
+    // complete data within the budget is returned
+    let mut reader = TestReader { chunks: vec![Ok(vec![0x01; BLOCK_SIZE])] };
+    let data = read_blocks(&mut reader, BLOCK_SIZE, None, Some(Duration::from_secs(1))).unwrap();
+    assert_eq!(data.len(), BLOCK_SIZE);
+}
+
+#[test]
+fn test_read_blocks_connection_reset() {
+    let mut reader = TestReader { chunks: vec![Ok(vec![0x01; BLOCK_SIZE]), Ok(vec![0x02; 16]), Err(std::io::Error::from(std::io::ErrorKind::ConnectionReset))] };
+    let err = read_blocks(&mut reader, BLOCK_SIZE, None, None).unwrap_err();
+    assert_eq!(format!("{}", err.downcast::<Errors>().unwrap()), "Receive error: connection reset");
+}
+
+#[test]
+fn test_read_blocks_unaligned() {
+    // the peer stalls in the middle of a block
+    let mut reader = TestReader { chunks: vec![Ok(vec![0x01; BLOCK_SIZE]), Ok(vec![0x02; 16])] };
+    let err = read_blocks(&mut reader, BLOCK_SIZE, None, None).unwrap_err();
+    assert_eq!(format!("{}", err.downcast::<Errors>().unwrap()), "Receive error: timeout waiting for the rest of a block after 48 bytes");
+
+    // the peer closes the connection in the middle of a block
+    let mut reader = TestReader { chunks: vec![Ok(vec![0x01; 16]), Ok(Vec::new())] };
+    let err = read_blocks(&mut reader, BLOCK_SIZE, None, None).unwrap_err();
+    assert_eq!(format!("{}", err.downcast::<Errors>().unwrap()), "Receive error: connection closed mid-block");
+
+    // a closed connection after complete blocks ends the read
+    let mut reader = TestReader { chunks: vec![Ok(vec![0x01; BLOCK_SIZE]), Ok(Vec::new())] };
+    assert_eq!(read_blocks(&mut reader, BLOCK_SIZE, None, None).unwrap().len(), BLOCK_SIZE);
+}
+
+#[test]
+fn test_read_blocks_chunk_size() {
+    let mut reader = TestReader { chunks: vec![Ok((0..=255).collect()), Ok(vec![0xaa; 64]), Ok(vec![0x55; 1024])] };
+    let data = read_blocks(&mut reader, 512, None, None).unwrap();
+    assert_eq!(data.len(), 256 + 64 + 1024);
+    assert_eq!(data[..256], (0..=255).collect::<Vec<u8>>());
+    assert_eq!(data[256..320], [0xaa; 64]);
+    assert_eq!(data[320..], [0x55; 1024]);
+}
+
+#[test]
+fn test_read_blocks_cancelled() {
+    let cancel_token = Arc::new(AtomicBool::new(false));
+    let mut reader = CancelReader {
+        reader: TestReader { chunks: vec![Ok(vec![0x01; BLOCK_SIZE]), Ok(vec![0x02; BLOCK_SIZE]), Ok(vec![0x03; BLOCK_SIZE])] },
+        cancel_token: cancel_token.clone(),
+    };
+    let err = read_blocks(&mut reader, BLOCK_SIZE, Some(&cancel_token), None).unwrap_err();
+    assert_eq!(format!("{}", err.downcast::<Errors>().unwrap()), "Cancelled");
+    assert_eq!(reader.reader.chunks.len(), 2);
+}
+
+#[test]
+fn test_cancel_disconnects() {
+    let handler = |request: Frame| {
+        let mut response = Frame::new();
+        if request.get_item(tags::RSCP::AUTHENTICATION.into()).is_ok() {
+            response.push_item(Item::new_tag(tags::RSCP::AUTHENTICATION, 10u8));
+        } else {
+            response.push_item(Item::new_tag(tags::INFO::SERIAL_NUMBER, "serial".to_string()));
+        }
+        response
+    };
+    let first_port = test_server("RSCP_KEY", 2, handler);
+    let second_port = test_server("RSCP_KEY", 2, handler);
+
+    let mut c = Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    let cancel_token = Arc::new(AtomicBool::new(false));
+    c.set_cancel_token(cancel_token.clone());
+    c.connect("127.0.0.1", Some(first_port)).unwrap();
+
+    let frame = Frame::request_tags(&[tags::INFO::SERIAL_NUMBER.into()]);
+    c.send(&frame).unwrap();
+    cancel_token.store(true, Ordering::Relaxed);
+    assert_eq!(format!("{}", c.receive().unwrap_err()), "Cancelled");
+    assert!(!c.connected);
+    assert_eq!(format!("{}", c.send_receive_frame(&frame).unwrap_err()), "Not Connected");
+    c.disconnect().unwrap();
+
+    // a new connection starts a new encryption chain
+    cancel_token.store(false, Ordering::Relaxed);
+    c.connect("127.0.0.1", Some(second_port)).unwrap();
+    assert_eq!(c.send_receive_frame(&frame).unwrap().get_item_data::<String>(tags::INFO::SERIAL_NUMBER.into()).unwrap(), "serial");
+}
+
+#[test]
+fn test_set_read_budget() {
+    let mut c = Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    assert_eq!(c.read_budget, Some(DEFAULT_READ_BUDGET));
+    c.set_read_budget(Duration::from_secs(60));
+    assert_eq!(c.read_budget, Some(Duration::from_secs(60)));
+}
+
+#[test]
+fn test_set_read_chunk_size() {
+    let mut c = Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    assert_eq!(c.read_chunk_size, BLOCK_SIZE);
+    c.set_read_chunk_size(1000);
+    assert_eq!(c.read_chunk_size, 1024);
+    c.set_read_chunk_size(4096);
+    assert_eq!(c.read_chunk_size, 4096);
+    c.set_read_chunk_size(0);
+    assert_eq!(c.read_chunk_size, BLOCK_SIZE);
+}
+
+#[test]
+fn test_connect_info() {
+    let port = test_server("RSCP_KEY", 1, |request| {
+        let auth = request.get_item(tags::RSCP::AUTHENTICATION.into()).unwrap();
+        assert_eq!(auth.get_item_data::<String>(tags::RSCP::AUTHENTICATION_USER.into()).unwrap(), "RSCP_USER");
+        assert_eq!(auth.get_item_data::<String>(tags::RSCP::AUTHENTICATION_PASSWORD.into()).unwrap(), "RSCP_PASSWORD");
+        let mut response = Frame::new();
+        response.push_item(Item::new_tag(tags::RSCP::AUTHENTICATION, 10u8));
+        response
+    });
+
+    let mut c = Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    let connect_info = c.connect("127.0.0.1", Some(port)).unwrap();
+    assert_eq!(connect_info, ConnectInfo { user_level: UserLevel::User, protocol_version: 0x01 });
+    c.disconnect().unwrap();
+}
+
+#[test]
+fn test_send_raw() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        stream.set_read_timeout(Some(std::time::Duration::from_millis(100))).unwrap();
+        let data = read_blocks(&mut stream, BLOCK_SIZE, None, None).unwrap();
+        stream.write_all(&data).unwrap();
+    });
+
+    let mut c = Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    let stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+    stream.set_read_timeout(Some(std::time::Duration::from_millis(500))).unwrap();
+    c.connection = Some(Arc::new(Mutex::new(stream)));
+    c.connected = true;
+
+    let data: Vec<u8> = (0..64).collect();
+    assert_eq!(c.send_raw(&data).unwrap(), data);
+}
+
+#[test]
+fn test_send_receive_pipelined() {
+    let port = test_server("RSCP_KEY", 3, |request| {
+        let mut response = Frame::new();
+        if request.get_item(tags::RSCP::AUTHENTICATION.into()).is_ok() {
+            response.push_item(Item::new_tag(tags::RSCP::AUTHENTICATION, 10u8));
+        } else if request.get_item(tags::INFO::SERIAL_NUMBER.into()).is_ok() {
+            response.push_item(Item::new_tag(tags::INFO::SERIAL_NUMBER, "serial".to_string()));
+        } else {
+            response.push_item(Item::new_tag(tags::INFO::SW_RELEASE, "release".to_string()));
+        }
+        response
+    });
+
+    let mut c = Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    c.connect("127.0.0.1", Some(port)).unwrap();
+
+    let mut serial_frame = Frame::new();
+    serial_frame.push_item(Item::from_tag(tags::INFO::SERIAL_NUMBER));
+    let mut release_frame = Frame::new();
+    release_frame.push_item(Item::from_tag(tags::INFO::SW_RELEASE));
+
+    c.send(&serial_frame).unwrap();
+    c.send(&release_frame).unwrap();
+    assert_eq!(c.receive().unwrap().get_item_data::<String>(tags::INFO::SERIAL_NUMBER.into()).unwrap(), "serial");
+    assert_eq!(c.receive().unwrap().get_item_data::<String>(tags::INFO::SW_RELEASE.into()).unwrap(), "release");
+    c.disconnect().unwrap();
+}
+
+#[test]
+fn test_send_batch() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        stream.set_read_timeout(Some(std::time::Duration::from_millis(100))).unwrap();
+        let mut data = Vec::new();
+        while data.is_empty() {
+            data = read_blocks(&mut stream, BLOCK_SIZE, None, None).unwrap();
+        }
+        sender.send(data).unwrap();
+    });
+
+    let mut c = Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    let stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+    c.connection = Some(Arc::new(Mutex::new(stream)));
+    c.connected = true;
+
+    let frames = [Frame::request_tags(&[tags::INFO::SERIAL_NUMBER.into()]), Frame::request_tags(&[tags::INFO::SW_RELEASE.into()])];
+    c.send_batch(&frames).unwrap();
+
+    // the frames are chained by the encryption like sequential sends
+    let mut enc_processor = RscpEncryption::new("RSCP_KEY");
+    let mut expected = Vec::new();
+    for frame in &frames {
+        expected.extend(enc_processor.encrypt(frame.to_bytes().unwrap()).unwrap());
+    }
+    assert_eq!(receiver.recv_timeout(Duration::from_secs(5)).unwrap(), expected);
+
+    // an invalid frame fails before anything is sent
+    let mut invalid_frame = Frame::new();
+    invalid_frame.push_item(Item::new_tag(tags::INFO::SERIAL_NUMBER, vec![0u8; 70000]));
+    assert!(c.send_batch(&[frames[0].clone(), invalid_frame]).is_err());
+}
+
+#[test]
+fn test_receive_split_frame() {
+    let serial = "S10-1234567890123456789012345678901234567890";
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut enc_processor = RscpEncryption::new("RSCP_KEY");
+        let mut response = Frame::new();
+        response.push_item(Item::new_tag(tags::INFO::SERIAL_NUMBER, serial.to_string()));
+
+        // the rest of the frame follows after the read timeout of the client
+        let enc_data = enc_processor.encrypt(response.to_bytes().unwrap()).unwrap();
+        stream.write_all(&enc_data[..BLOCK_SIZE]).unwrap();
+        std::thread::sleep(Duration::from_millis(800));
+        stream.write_all(&enc_data[BLOCK_SIZE..]).unwrap();
+
+        // the frame is never completed
+        let enc_data = enc_processor.encrypt(response.to_bytes().unwrap()).unwrap();
+        stream.write_all(&enc_data[..BLOCK_SIZE]).unwrap();
+        std::thread::sleep(Duration::from_millis(1500));
+    });
+
+    let mut c = Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    let stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+    stream.set_read_timeout(Some(Duration::from_millis(500))).unwrap();
+    c.connection = Some(Arc::new(Mutex::new(stream)));
+    c.connected = true;
+
+    let frame = c.receive().unwrap();
+    assert_eq!(frame.get_item_data::<String>(tags::INFO::SERIAL_NUMBER.into()).unwrap(), serial);
+
+    let err = c.receive().unwrap_err();
+    assert_eq!(format!("{}", err), "Receive error: incomplete frame, received 32 bytes");
+    assert!(c.pending.is_empty());
+}
+
+#[test]
+fn test_send_frozen() {
+    let port = test_server("RSCP_KEY", 3, |request| {
+        let mut response = Frame::new();
+        if request.get_item(tags::RSCP::AUTHENTICATION.into()).is_ok() {
+            response.push_item(Item::new_tag(tags::RSCP::AUTHENTICATION, 10u8));
+        } else {
+            response.push_item(Item::new_tag(tags::INFO::SERIAL_NUMBER, "serial".to_string()));
+        }
+        response
+    });
+
+    let mut c = Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    c.connect("127.0.0.1", Some(port)).unwrap();
+
+    let mut serial_frame = Frame::new();
+    serial_frame.push_item(Item::from_tag(tags::INFO::SERIAL_NUMBER));
+    let frozen_frame = serial_frame.freeze().unwrap();
+
+    for _ in 0..2 {
+        c.send_frozen(&frozen_frame).unwrap();
+        assert_eq!(c.receive().unwrap().get_item_data::<String>(tags::INFO::SERIAL_NUMBER.into()).unwrap(), "serial");
+    }
+    c.disconnect().unwrap();
+}
+
+#[test]
+fn test_flush_shutdown() {
+    let port = test_server("RSCP_KEY", 1, |_| {
+        let mut response = Frame::new();
+        response.push_item(Item::new_tag(tags::RSCP::AUTHENTICATION, 10u8));
+        response
+    });
+
+    let mut c = Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    assert_eq!(format!("{}", c.flush().unwrap_err()), "Not Connected");
+    c.connect("127.0.0.1", Some(port)).unwrap();
+
+    c.flush().unwrap();
+    c.shutdown(Shutdown::Read).unwrap();
+    c.flush().unwrap();
+    c.shutdown(Shutdown::Write).unwrap();
+    assert_eq!(format!("{}", c.flush().unwrap_err()), "Not Connected");
+    assert_eq!(format!("{}", c.shutdown(Shutdown::Both).unwrap_err()), "Not Connected");
+}
+
+#[test]
+fn test_request_streaming() {
+    let port = test_server("RSCP_KEY", 2, |request| {
+        let mut response = Frame::new();
+        if request.get_item(tags::RSCP::AUTHENTICATION.into()).is_ok() {
+            response.push_item(Item::new_tag(tags::RSCP::AUTHENTICATION, 10u8));
+        } else {
+            for row in 0..3 {
+                response.push_item(Item::new_tag(tags::DB::HISTORY_DATA_DAY, vec![Item::new_tag(tags::DB::VALUE_CONTAINER, vec![
+                    Item::new_tag(tags::DB::GRAPH_INDEX, row as f32),
+                    Item::new_tag(tags::DB::DC_POWER, 100.0f32 * row as f32),
+                ])]));
+            }
+        }
+        response
+    });
+
+    let mut c = Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    c.connect("127.0.0.1", Some(port)).unwrap();
+
+    let mut frame = Frame::new();
+    frame.push_item(Item::from_tag(tags::DB::HISTORY_DATA_DAY));
+    let mut rows = Vec::new();
+    c.request_streaming(&frame, |item| {
+        let value_container = item.get_item(tags::DB::VALUE_CONTAINER.into()).unwrap();
+        rows.push(*value_container.get_item_data::<f32>(tags::DB::DC_POWER.into()).unwrap());
+    })
+    .unwrap();
+    assert_eq!(rows, vec![0.0, 100.0, 200.0]);
+    c.disconnect().unwrap();
+}
+
+#[test]
+fn test_request_streaming_after_pipelined() {
+    let port = test_server("RSCP_KEY", 4, |request| {
+        let mut response = Frame::new();
+        if request.get_item(tags::RSCP::AUTHENTICATION.into()).is_ok() {
+            response.push_item(Item::new_tag(tags::RSCP::AUTHENTICATION, 10u8));
+        } else if request.get_item(tags::INFO::SERIAL_NUMBER.into()).is_ok() {
+            response.push_item(Item::new_tag(tags::INFO::SERIAL_NUMBER, "serial".to_string()));
+        } else if request.get_item(tags::INFO::SW_RELEASE.into()).is_ok() {
+            response.push_item(Item::new_tag(tags::INFO::SW_RELEASE, "release".to_string()));
+        } else {
+            response.push_item(Item::new_tag(tags::INFO::UTC_TIME, chrono::Utc::now()));
+        }
+        response
+    });
+
+    let mut c = Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    c.connect("127.0.0.1", Some(port)).unwrap();
+
+    let mut serial_frame = Frame::new();
+    serial_frame.push_item(Item::from_tag(tags::INFO::SERIAL_NUMBER));
+    let mut release_frame = Frame::new();
+    release_frame.push_item(Item::from_tag(tags::INFO::SW_RELEASE));
+
+    // the response to the release request is left pending
+    c.send(&serial_frame).unwrap();
+    c.send(&release_frame).unwrap();
+    assert_eq!(c.receive().unwrap().get_item_data::<String>(tags::INFO::SERIAL_NUMBER.into()).unwrap(), "serial");
+
+    let mut frame = Frame::new();
+    frame.push_item(Item::from_tag(tags::INFO::UTC_TIME));
+    let mut received = Vec::new();
+    c.request_streaming(&frame, |item| received.push(item.tag)).unwrap();
+    assert_eq!(received, vec![tags::INFO::UTC_TIME as u32]);
+    c.disconnect().unwrap();
+}
+
+#[test]
+fn test_ping() {
+    let port = test_server("RSCP_KEY", 2, |request| {
+        let mut response = Frame::new();
+        if request.get_item(tags::RSCP::AUTHENTICATION.into()).is_ok() {
+            response.push_item(Item::new_tag(tags::RSCP::AUTHENTICATION, 10u8));
+        } else {
+            assert!(request.get_item(tags::INFO::UTC_TIME.into()).is_ok());
+            response.push_item(Item::new_tag(tags::INFO::UTC_TIME, chrono::Utc::now()));
+        }
+        response
+    });
+
+    let mut c = Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    c.connect("127.0.0.1", Some(port)).unwrap();
+    assert!(c.ping().unwrap() >= Duration::ZERO);
+    c.disconnect().unwrap();
+}
+
+#[test]
+fn test_device_info() {
+    let port = test_server("RSCP_KEY", 2, |request| {
+        let mut response = Frame::new();
+        if request.get_item(tags::RSCP::AUTHENTICATION.into()).is_ok() {
+            response.push_item(Item::new_tag(tags::RSCP::AUTHENTICATION, 10u8));
+        } else {
+            response.push_item(Item::new_tag(tags::INFO::SERIAL_NUMBER, "serial".to_string()));
+            response.push_item(Item::new_tag(tags::INFO::MAC_ADDRESS, "00:11:22:33:44:55".to_string()));
+            response.push_item(Item::new_tag(tags::INFO::SW_RELEASE, "release".to_string()));
+            response.push_item(Item::new_tag(tags::INFO::PRODUCTION_DATE, "date".to_string()));
+            response.push_item(Item::new_tag(tags::INFO::PLATFORM_TYPE, 3i32));
+        }
+        response
+    });
+
+    let mut c = Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    c.connect("127.0.0.1", Some(port)).unwrap();
+    let info = c.device_info().unwrap();
+    assert_eq!(info.serial_number, "serial");
+    assert_eq!(info.platform_type, 3);
+    c.disconnect().unwrap();
+}
+
+#[test]
+fn test_last_response_bytes() {
+    let port = test_server("RSCP_KEY", 2, |request| {
+        let mut response = Frame::new();
+        if request.get_item(tags::RSCP::AUTHENTICATION.into()).is_ok() {
+            response.push_item(Item::new_tag(tags::RSCP::AUTHENTICATION, 10u8));
+        } else {
+            response.push_item(Item::new_tag(tags::INFO::SERIAL_NUMBER, "serial".to_string()));
+            response.set_protocol_version(0x03);
+        }
+        response
+    });
+
+    let mut c = Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    assert!(c.last_response_bytes().is_empty());
+    c.connect("127.0.0.1", Some(port)).unwrap();
+
+    let frame = Frame::request_tags(&[tags::INFO::SERIAL_NUMBER.into()]);
+    assert_eq!(format!("{}", c.send_receive_frame(&frame).unwrap_err()), "Unsupported protocol version, got 19");
+    let data = c.last_response_bytes();
+    assert_eq!(data[..4], [0xe3, 0xdc, 0x00, 0x13]);
+    assert_eq!(data.len() % BLOCK_SIZE, 0);
+    c.disconnect().unwrap();
+}
+
+#[test]
+fn test_set_credentials() {
+    let mut c = Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    c.set_credentials("NEW_USER".to_string(), "NEW_PASSWORD".to_string());
+
+    let frame = c.auth_frame();
+    let auth = frame.get_item(tags::RSCP::AUTHENTICATION.into()).unwrap();
+    assert_eq!(auth.get_item_data::<String>(tags::RSCP::AUTHENTICATION_USER.into()).unwrap(), "NEW_USER");
+    assert_eq!(auth.get_item_data::<String>(tags::RSCP::AUTHENTICATION_PASSWORD.into()).unwrap(), "NEW_PASSWORD");
+}
+
+#[test]
+fn test_authenticate() {
+    let mut user_levels = vec![10u8, 20u8].into_iter();
+    let port = test_server("RSCP_KEY", 2, move |_| {
+        let mut response = Frame::new();
+        response.push_item(Item::new_tag(tags::RSCP::AUTHENTICATION, user_levels.next().unwrap()));
+        response
+    });
+
+    let mut c = Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    assert_eq!(format!("{}", c.authenticate().unwrap_err()), "Not Connected");
+
+    let info = c.connect("127.0.0.1", Some(port)).unwrap();
+    assert_eq!(info.user_level, UserLevel::from(10u8));
+    assert_eq!(c.authenticate().unwrap(), UserLevel::from(20u8));
+    c.disconnect().unwrap();
+}
+
+#[cfg(feature = "proxy")]
+#[test]
+fn test_connect_proxy() {
+    let port = test_server("RSCP_KEY", 1, |_| {
+        let mut response = Frame::new();
+        response.push_item(Item::new_tag(tags::RSCP::AUTHENTICATION, 10u8));
+        response
+    });
+
+    // SOCKS5 proxy relaying a single connection
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let proxy = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut greeting = [0u8; 3];
+        stream.read_exact(&mut greeting).unwrap();
+        stream.write_all(&[0x05, 0x00]).unwrap();
+        let mut request = [0u8; 5 + 9 + 2];
+        stream.read_exact(&mut request).unwrap();
+        assert_eq!(&request[5..14], b"localhost");
+        let target = TcpStream::connect(("127.0.0.1", u16::from_be_bytes([request[14], request[15]]))).unwrap();
+        stream.write_all(&[0x05, 0x00, 0x00, 0x01, 127, 0, 0, 1, 0x00, 0x00]).unwrap();
+
+        let (mut client_read, mut target_write) = (stream.try_clone().unwrap(), target.try_clone().unwrap());
+        std::thread::spawn(move || std::io::copy(&mut client_read, &mut target_write));
+        let (mut target_read, mut client_write) = (target, stream);
+        std::io::copy(&mut target_read, &mut client_write).ok();
+    });
+
+    let mut c = Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    c.set_proxy(Some(proxy));
+    let info = c.connect("localhost", Some(port)).unwrap();
+    assert_eq!(info.user_level, UserLevel::from(10u8));
+    c.disconnect().unwrap();
+}
+
+#[test]
+fn test_connect_key_invalid() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    std::thread::spawn(move || {
+        let mut enc_processor = RscpEncryption::new("RSCP_KEY");
+        let (mut stream, _) = listener.accept().unwrap();
+        stream.set_read_timeout(Some(std::time::Duration::from_millis(100))).unwrap();
+        // the request can not be decrypted, answer anyway
+        while read_blocks(&mut stream, BLOCK_SIZE, None, None).unwrap().is_empty() {}
+        let mut response = Frame::new();
+        response.push_item(Item::new_tag(tags::RSCP::AUTHENTICATION, 10u8));
+        stream.write_all(&enc_processor.encrypt(response.to_bytes().unwrap()).unwrap()).unwrap();
+    });
+
+    let mut c = Client::new("WRONG_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    let err = c.connect("127.0.0.1", Some(port)).unwrap_err();
+    assert_eq!(format!("{}", err), "Invalid RSCP key");
+}
+
+#[test]
+fn test_retry() {
+    use crate::ItemContainer;
+
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let server_requests = requests.clone();
+    // the first and second connection are closed after the authentication and one request
+    let port = test_server_sessions("RSCP_KEY", vec![1, 2, 2], move |request| {
+        let mut response = Frame::new();
+        if request.get_item(tags::RSCP::AUTHENTICATION.into()).is_ok() {
+            response.push_item(Item::new_tag(tags::RSCP::AUTHENTICATION, 10u8));
+        } else {
+            server_requests.lock().unwrap().push(request.items()[0].tag);
+            response.push_item(Item::new(request.items()[0].tag | 0x00800000, true));
+        }
+        response
+    });
+
+    let mut c = Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    c.set_retries(1);
+    c.connect("127.0.0.1", Some(port)).unwrap();
+
+    // write requests are not resent by default
+    let write_frame = crate::se::set_ep_reserve(20.0);
+    assert!(c.send_receive_frame(&write_frame).is_err());
+
+    let read_frame = Frame::request_tags(&[tags::EMS::POWER_PV.into()]);
+    assert!(c.send_receive_frame(&read_frame).is_ok());
+
+    c.set_retry_writes(true);
+    assert!(c.send_receive_frame(&write_frame).is_ok());
+    assert_eq!(*requests.lock().unwrap(), vec![tags::EMS::POWER_PV as u32, tags::SE::SET_EP_RESERVE as u32]);
+    c.disconnect().unwrap();
+}
+
+/// starts a local RSCP server answering a number of frames using the handler, returns the port
+#[cfg(test)]
+fn test_server<F: FnMut(Frame) -> Frame + Send + 'static>(rscp_key: &str, frames: usize, handler: F) -> u16 {
+    test_server_sessions(rscp_key, vec![frames], handler)
+}
+
+/// starts a local RSCP server accepting a connection for each session, each connection is closed
+/// after answering the number of frames of the session, returns the port
+#[cfg(test)]
+fn test_server_sessions<F: FnMut(Frame) -> Frame + Send + 'static>(rscp_key: &str, sessions: Vec<usize>, mut handler: F) -> u16 {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let rscp_key = rscp_key.to_string();
+    std::thread::spawn(move || {
+        for frames in sessions {
+            let mut enc_processor = RscpEncryption::new(&rscp_key);
+            let (mut stream, _) = listener.accept().unwrap();
+            stream.set_read_timeout(Some(std::time::Duration::from_millis(100))).unwrap();
+            let mut handled = 0;
+            while handled < frames {
+                let enc_data = read_blocks(&mut stream, BLOCK_SIZE, None, None).unwrap();
+                if enc_data.is_empty() {
+                    continue;
+                }
+                let mut data = enc_processor.decrypt(enc_data).unwrap();
+                while !data.is_empty() && handled < frames {
+                    let size = frame_size(&data).unwrap().div_ceil(BLOCK_SIZE) * BLOCK_SIZE;
+                    let remaining = data.split_off(size);
+                    let request = Frame::from_bytes(std::mem::replace(&mut data, remaining)).unwrap();
+                    let response = handler(request).to_bytes().unwrap();
+                    stream.write_all(&enc_processor.encrypt(response).unwrap()).unwrap();
+                    handled += 1;
+                }
+            }
+        }
+    });
+    port
+}
+
+/// reader returning a single byte after each delay
+#[cfg(test)]
+struct SlowReader {
+    delay: Duration,
+}
+
+#[cfg(test)]
+impl Read for SlowReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        std::thread::sleep(self.delay);
+        buf[0] = 0x01;
+        Ok(1)
+    }
+}
+
+/// reader setting the cancel token after the first read
+#[cfg(test)]
+struct CancelReader {
+    reader: TestReader,
+    cancel_token: Arc<AtomicBool>,
+}
+
+#[cfg(test)]
+impl Read for CancelReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let len = self.reader.read(buf)?;
+        self.cancel_token.store(true, Ordering::Relaxed);
+        Ok(len)
     }
 }
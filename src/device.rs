@@ -0,0 +1,98 @@
+use anyhow::{bail, Result};
+
+use crate::tags::{self, TagGroup};
+use crate::{Errors, Item};
+
+/// bitmask of the tag without group and response bit
+const TAG_ID_MASK: u32 = 0x007fffff;
+
+/// State of a device, shared by the `DEVICE_STATE` containers of the tag groups
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct DeviceState {
+    /// device is connected
+    pub connected: bool,
+
+    /// device is working
+    pub working: bool,
+
+    /// device is in service
+    pub in_service: bool,
+}
+
+/// Returns the device state parsed from a `DEVICE_STATE` container of any tag group
+///
+/// # Arguments
+///
+/// * `item` - the device state container item
+///
+/// # Examples
+///
+/// ```
+/// use rscp::{device, tags, Item};
+/// let item = Item::new(tags::BAT::DEVICE_STATE.into(), vec![
+///     Item::new(tags::BAT::DEVICE_CONNECTED.into(), true),
+///     Item::new(tags::BAT::DEVICE_WORKING.into(), true),
+///     Item::new(tags::BAT::DEVICE_IN_SERVICE.into(), false),
+/// ]);
+/// let state = device::parse_device_state(&item).unwrap();
+/// assert!(state.connected);
+/// ```
+pub fn parse_device_state(item: &Item) -> Result<DeviceState> {
+    // the LED group uses different ids for the device state
+    let (connected_id, working_id, in_service_id) = if TagGroup::from((item.tag >> 24) as u8) == TagGroup::LED {
+        (tags::LED::DEVICE_CONNECTED as u32, tags::LED::DEVICE_WORKING as u32, tags::LED::DEVICE_IN_SERVICE as u32)
+    } else {
+        (tags::BAT::DEVICE_CONNECTED as u32, tags::BAT::DEVICE_WORKING as u32, tags::BAT::DEVICE_IN_SERVICE as u32)
+    };
+
+    let container = item.as_container()?;
+    let get_state = |id: u32, name: &str| -> Result<bool> {
+        match container.iter().find(|sub_item| sub_item.tag & TAG_ID_MASK == id & TAG_ID_MASK) {
+            Some(sub_item) => sub_item.as_bool(),
+            None => bail!(Errors::Parse(format!("Missing {} in device state", name))),
+        }
+    };
+
+    Ok(DeviceState {
+        connected: get_state(connected_id, "DEVICE_CONNECTED")?,
+        working: get_state(working_id, "DEVICE_WORKING")?,
+        in_service: get_state(in_service_id, "DEVICE_IN_SERVICE")?,
+    })
+}
+
+/// ################################################
+///      TEST TEST TEST
+/// ################################################
+
+#[test]
+fn test_parse_device_state() {
+    let item = Item::new_tag(tags::PVI::DEVICE_STATE, vec![
+        Item::new_tag(tags::PVI::DEVICE_CONNECTED, true),
+        Item::new_tag(tags::PVI::DEVICE_WORKING, false),
+        Item::new_tag(tags::PVI::DEVICE_IN_SERVICE, true),
+    ]);
+    assert_eq!(parse_device_state(&item).unwrap(), DeviceState { connected: true, working: false, in_service: true });
+
+    let item = Item::new_tag(tags::LED::DEVICE_STATE, vec![
+        Item::new_tag(tags::LED::DEVICE_CONNECTED, true),
+        Item::new_tag(tags::LED::DEVICE_WORKING, true),
+        Item::new_tag(tags::LED::DEVICE_IN_SERVICE, false),
+    ]);
+    assert_eq!(parse_device_state(&item).unwrap(), DeviceState { connected: true, working: true, in_service: false });
+
+    let item = Item::new_tag(tags::WB::DEVICE_STATE, vec![Item::new_tag(tags::WB::DEVICE_CONNECTED, true)]);
+    assert_eq!(format!("{}", parse_device_state(&item).unwrap_err()), "Frame parse error: Missing DEVICE_WORKING in device state");
+
+    let item = Item::new_tag(tags::WB::DEVICE_STATE, true);
+    assert!(parse_device_state(&item).is_err());
+}
+
+#[test]
+fn test_device_states() {
+    let item = Item::new_tag(tags::DCDC::STATE, 5u8);
+    assert_eq!(item.as_dcdc_state().unwrap(), 5);
+    let item = Item::new_tag(tags::PVI::STATE, 0u8);
+    assert_eq!(item.as_pvi_state().unwrap(), 0);
+    let item = Item::new_tag(tags::PVI::STATE, "FEEDING".to_string());
+    assert_eq!(item.as_pvi_state().unwrap_err().to_string(), "Type mismatch, expected UChar8 got String");
+}
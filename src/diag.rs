@@ -0,0 +1,125 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+
+use crate::{tags, Frame, GetItem, Item};
+
+/// Diagnostic issue reported by the device
+#[derive(Clone, PartialEq, Debug)]
+pub struct DiagIssue {
+    /// error code of the issue
+    pub code: u32,
+
+    /// time the issue arose
+    pub arose_at: DateTime<Utc>,
+
+    /// duration the issue endured
+    pub duration: Duration,
+
+    /// error message of the issue
+    pub message: String,
+}
+
+/// Returns frame requesting the current issues
+pub fn request_current_issues() -> Frame {
+    let mut frame = Frame::new();
+    frame.push_item(Item::from_tag(tags::DIAG::CURRENT_ISSUES));
+    frame
+}
+
+/// Returns frame requesting the reported issues
+pub fn request_reported_issues() -> Frame {
+    let mut frame = Frame::new();
+    frame.push_item(Item::from_tag(tags::DIAG::REPORTED_ISSUES));
+    frame
+}
+
+/// Returns the issues of `CURRENT_ISSUES` and `REPORTED_ISSUES` containers in the frame
+///
+/// # Arguments
+///
+/// * `frame` - the response frame
+///
+/// # Examples
+///
+/// ```no_run
+/// use rscp::diag;
+/// let mut c = rscp::Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+/// c.connect("energy.storage.local", None).unwrap();
+/// let result_frame = c.send_receive_frame(&diag::request_current_issues()).unwrap();
+/// for issue in diag::parse_issues(&result_frame).unwrap() {
+///     println!("{}: {}", issue.code, issue.message);
+/// }
+/// ```
+pub fn parse_issues(frame: &Frame) -> Result<Vec<DiagIssue>> {
+    let mut issues = Vec::new();
+    for tag in [tags::DIAG::CURRENT_ISSUES, tags::DIAG::REPORTED_ISSUES] {
+        if let Ok(container) = frame.get_item(tag.into()) {
+            for issue in container.as_container()? {
+                if issue.tag == tags::DIAG::ISSUE as u32 {
+                    issues.push(parse_issue(issue)?);
+                }
+            }
+        }
+    }
+    Ok(issues)
+}
+
+/// Returns issue parsed from `ISSUE` container
+///
+/// # Arguments
+///
+/// * `issue` - the issue container item
+fn parse_issue(issue: &Item) -> Result<DiagIssue> {
+    let endure_time = issue.get_item(tags::DIAG::ENDURE_TIME.into())?;
+    let duration = match endure_time.as_u32() {
+        Ok(seconds) => Duration::seconds(seconds as i64),
+        Err(_) => endure_time.as_timestamp()? - DateTime::UNIX_EPOCH,
+    };
+    Ok(DiagIssue {
+        code: issue.get_item(tags::DIAG::ERR_CODE.into())?.as_u32()?,
+        arose_at: issue.get_item(tags::DIAG::TIME_ARISED.into())?.as_timestamp()?,
+        duration,
+        message: issue.get_item(tags::DIAG::ERR_MSG.into())?.as_string()?.to_string(),
+    })
+}
+
+/// ################################################
+///      TEST TEST TEST
+/// ################################################
+
+#[test]
+fn test_parse_issues() {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::DIAG::CURRENT_ISSUES, vec![
+        Item::new_tag(tags::DIAG::ISSUE, vec![
+            Item::new_tag(tags::DIAG::ERR_CODE, 0x1234u32),
+            Item::new_tag(tags::DIAG::ENDURE_TIME, DateTime::<Utc>::from_timestamp(3600, 0).unwrap()),
+            Item::new_tag(tags::DIAG::TIME_ARISED, DateTime::<Utc>::from_timestamp(1700000000, 0).unwrap()),
+            Item::new_tag(tags::DIAG::ERR_MSG, "Battery error".to_string()),
+        ]),
+        Item::new_tag(tags::DIAG::ISSUE, vec![
+            Item::new_tag(tags::DIAG::ERR_CODE, 0x42u32),
+            Item::new_tag(tags::DIAG::ENDURE_TIME, 120u32),
+            Item::new_tag(tags::DIAG::TIME_ARISED, DateTime::<Utc>::from_timestamp(1700000100, 0).unwrap()),
+            Item::new_tag(tags::DIAG::ERR_MSG, "Grid error".to_string()),
+        ]),
+    ]));
+
+    let issues = parse_issues(&frame).unwrap();
+    assert_eq!(issues, vec![
+        DiagIssue {
+            code: 0x1234,
+            arose_at: DateTime::<Utc>::from_timestamp(1700000000, 0).unwrap(),
+            duration: Duration::hours(1),
+            message: "Battery error".to_string(),
+        },
+        DiagIssue {
+            code: 0x42,
+            arose_at: DateTime::<Utc>::from_timestamp(1700000100, 0).unwrap(),
+            duration: Duration::minutes(2),
+            message: "Grid error".to_string(),
+        },
+    ]);
+
+    assert!(parse_issues(&request_reported_issues()).is_err());
+}
@@ -0,0 +1,476 @@
+use anyhow::{bail, Result};
+use std::collections::HashMap;
+
+use crate::{tags, Errors, Frame, GetItem, Item};
+
+/// Type of an idle period
+#[derive(Copy, Clone, PartialEq, Debug)]
+#[repr(u8)]
+pub enum IdlePeriodType {
+    /// battery is not charged
+    Charge = 0,
+    /// battery is not discharged
+    Discharge = 1,
+}
+
+/// Idle period of the battery, the time is given per week day
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct IdlePeriod {
+    /// type of the idle period
+    pub period_type: IdlePeriodType,
+
+    /// week day, 0 is monday
+    pub day: u8,
+
+    /// start time as hour and minute
+    pub start: (u8, u8),
+
+    /// end time as hour and minute
+    pub end: (u8, u8),
+
+    /// true if the idle period is active
+    pub active: bool,
+}
+
+impl IdlePeriod {
+    /// Returns builder for an idle period
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::ems::{IdlePeriod, IdlePeriodType};
+    /// let idle_period = IdlePeriod::builder()
+    ///     .period_type(IdlePeriodType::Charge)
+    ///     .day(0)
+    ///     .start(8, 0)
+    ///     .end(16, 30)
+    ///     .active(true)
+    ///     .build()
+    ///     .unwrap();
+    /// ```
+    pub fn builder() -> IdlePeriodBuilder {
+        IdlePeriodBuilder {
+            period: IdlePeriod {
+                period_type: IdlePeriodType::Charge,
+                day: 0,
+                start: (0, 0),
+                end: (0, 0),
+                active: false,
+            },
+        }
+    }
+
+    /// Returns the `IDLE_PERIOD` container item
+    pub fn to_item(&self) -> Item {
+        Item::container(tags::EMS::IDLE_PERIOD.into(), |c| {
+            c.item(Item::new_tag(tags::EMS::IDLE_PERIOD_TYPE, self.period_type as u8));
+            c.item(Item::new_tag(tags::EMS::IDLE_PERIOD_DAY, self.day));
+            c.item(Item::new_tag(tags::EMS::IDLE_PERIOD_ACTIVE, self.active));
+            c.container(tags::EMS::IDLE_PERIOD_START.into(), |c| {
+                c.item(Item::new_tag(tags::EMS::IDLE_PERIOD_HOUR, self.start.0));
+                c.item(Item::new_tag(tags::EMS::IDLE_PERIOD_MINUTE, self.start.1));
+            });
+            c.container(tags::EMS::IDLE_PERIOD_END.into(), |c| {
+                c.item(Item::new_tag(tags::EMS::IDLE_PERIOD_HOUR, self.end.0));
+                c.item(Item::new_tag(tags::EMS::IDLE_PERIOD_MINUTE, self.end.1));
+            });
+        })
+    }
+}
+
+/// Builder for an idle period, validates the values on build
+pub struct IdlePeriodBuilder {
+    /// the idle period to build
+    period: IdlePeriod,
+}
+
+impl IdlePeriodBuilder {
+    /// Sets the type of the idle period
+    pub fn period_type(mut self, period_type: IdlePeriodType) -> Self {
+        self.period.period_type = period_type;
+        self
+    }
+
+    /// Sets the week day, 0 is monday and 6 is sunday
+    pub fn day(mut self, day: u8) -> Self {
+        self.period.day = day;
+        self
+    }
+
+    /// Sets the start time
+    pub fn start(mut self, hour: u8, minute: u8) -> Self {
+        self.period.start = (hour, minute);
+        self
+    }
+
+    /// Sets the end time
+    pub fn end(mut self, hour: u8, minute: u8) -> Self {
+        self.period.end = (hour, minute);
+        self
+    }
+
+    /// Sets the idle period active
+    pub fn active(mut self, active: bool) -> Self {
+        self.period.active = active;
+        self
+    }
+
+    /// Returns the idle period, fails if day, hours or minutes are out of range or the start is not before the end
+    pub fn build(self) -> Result<IdlePeriod> {
+        let period = self.period;
+        if period.day > 6 {
+            bail!(Errors::InvalidValue(format!("Idle period day {} out of range 0-6", period.day)))
+        }
+        for (hour, minute) in [period.start, period.end] {
+            if hour > 23 {
+                bail!(Errors::InvalidValue(format!("Idle period hour {} out of range 0-23", hour)))
+            }
+            if minute > 59 {
+                bail!(Errors::InvalidValue(format!("Idle period minute {} out of range 0-59", minute)))
+            }
+        }
+        if period.start >= period.end {
+            bail!(Errors::InvalidValue(format!("Idle period start {:02}:{:02} is not before end {:02}:{:02}", period.start.0, period.start.1, period.end.0, period.end.1)))
+        }
+        Ok(period)
+    }
+}
+
+/// Returns frame to set the idle periods
+///
+/// # Arguments
+///
+/// * `periods` - the idle periods
+///
+/// # Examples
+///
+/// ```
+/// use rscp::ems::{self, IdlePeriod};
+/// let idle_period = IdlePeriod::builder().start(8, 0).end(16, 30).active(true).build().unwrap();
+/// let frame = ems::set_idle_periods(&[idle_period]);
+/// ```
+pub fn set_idle_periods(periods: &[IdlePeriod]) -> Frame {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::EMS::SET_IDLE_PERIODS, periods.iter().map(IdlePeriod::to_item).collect::<Vec<Item>>()));
+    frame
+}
+
+/// Returns heartbeat frame to keep long-lived connections active
+///
+/// # Examples
+///
+/// ```
+/// let frame = rscp::ems::alive_frame();
+/// assert!(frame.is_alive());
+/// ```
+pub fn alive_frame() -> Frame {
+    let mut frame = Frame::new();
+    frame.push_item(Item::from_tag(tags::EMS::ALIVE));
+    frame
+}
+
+/// Power settings of the battery
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct PowerSettings {
+    /// true if the power limits are used
+    pub power_limits_used: bool,
+
+    /// maximum charge power in watt
+    pub max_charge_power: u32,
+
+    /// maximum discharge power in watt
+    pub max_discharge_power: u32,
+
+    /// minimum power to start discharging in watt
+    pub discharge_start_power: u32,
+
+    /// true if the power save mode is enabled
+    pub powersave_enabled: bool,
+
+    /// true if the weather regulated charge is enabled
+    pub weather_regulated_charge_enabled: bool,
+}
+
+impl PowerSettings {
+    /// Returns frame requesting the power settings
+    pub fn request() -> Frame {
+        let mut frame = Frame::new();
+        frame.push_item(Item::from_tag(tags::EMS::GET_POWER_SETTINGS));
+        frame
+    }
+
+    /// Returns power settings parsed from the `GET_POWER_SETTINGS` container of the frame
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - the response frame
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rscp::ems::PowerSettings;
+    /// let mut c = rscp::Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    /// c.connect("energy.storage.local", None).unwrap();
+    /// let mut settings = PowerSettings::from_frame(&c.send_receive_frame(&PowerSettings::request()).unwrap()).unwrap();
+    /// settings.max_charge_power = 3000;
+    /// c.send_receive_frame(&settings.to_set_frame()).unwrap();
+    /// ```
+    pub fn from_frame(frame: &Frame) -> Result<Self> {
+        let container = frame.get_item(tags::EMS::GET_POWER_SETTINGS.into())?;
+        // the device responds with an error code instead of the container e.g. if access is denied
+        container.as_container()?;
+        Ok(Self {
+            power_limits_used: container.get_item(tags::EMS::POWER_LIMITS_USED.into())?.as_bool()?,
+            max_charge_power: container.get_item(tags::EMS::MAX_CHARGE_POWER.into())?.as_u32()?,
+            max_discharge_power: container.get_item(tags::EMS::MAX_DISCHARGE_POWER.into())?.as_u32()?,
+            discharge_start_power: container.get_item(tags::EMS::DISCHARGE_START_POWER.into())?.as_u32()?,
+            powersave_enabled: container.get_item(tags::EMS::POWERSAVE_ENABLED.into())?.as_bool()?,
+            weather_regulated_charge_enabled: container.get_item(tags::EMS::WEATHER_REGULATED_CHARGE_ENABLED.into())?.as_bool()?,
+        })
+    }
+
+    /// Returns frame to set all power settings
+    pub fn to_set_frame(&self) -> Frame {
+        let mut frame = Frame::new();
+        frame.push_item(Item::new_tag(tags::EMS::SET_POWER_SETTINGS, vec![
+            Item::new_tag(tags::EMS::POWER_LIMITS_USED, self.power_limits_used),
+            Item::new_tag(tags::EMS::MAX_CHARGE_POWER, self.max_charge_power),
+            Item::new_tag(tags::EMS::MAX_DISCHARGE_POWER, self.max_discharge_power),
+            Item::new_tag(tags::EMS::DISCHARGE_START_POWER, self.discharge_start_power),
+            Item::new_tag(tags::EMS::POWERSAVE_ENABLED, self.powersave_enabled),
+            Item::new_tag(tags::EMS::WEATHER_REGULATED_CHARGE_ENABLED, self.weather_regulated_charge_enabled),
+        ]));
+        frame
+    }
+}
+
+/// Returns frame requesting the regulator mode, strategy and the supported modes
+pub fn request_regulator() -> Frame {
+    Frame::request_tags(&[
+        tags::EMS::REGULATOR_MODE.into(),
+        tags::EMS::REGULATOR_STRATEGY.into(),
+        tags::EMS::SUPPORTED_REGULATOR_MODES.into(),
+    ])
+}
+
+/// Returns frame to set the regulator mode
+///
+/// # Arguments
+///
+/// * `mode` - the new raw regulator mode, the values are not documented
+///
+/// # Examples
+///
+/// ```
+/// use rscp::ems;
+/// let frame = ems::set_regulator_mode(1);
+/// ```
+pub fn set_regulator_mode(mode: u8) -> Frame {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::EMS::SET_REGULATOR_MODE, mode));
+    frame
+}
+
+/// Returns frame to set the regulator strategy
+///
+/// # Arguments
+///
+/// * `strategy` - the new raw regulator strategy, the values are not documented
+pub fn set_regulator_strategy(strategy: u8) -> Frame {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::EMS::SET_REGULATOR_STRATEGY, strategy));
+    frame
+}
+
+/// Returns the raw regulator mode of the `REGULATOR_MODE` item in the frame
+///
+/// # Arguments
+///
+/// * `frame` - the response frame
+///
+/// # Examples
+///
+/// ```no_run
+/// use rscp::ems;
+/// let mut c = rscp::Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+/// c.connect("energy.storage.local", None).unwrap();
+/// let result_frame = c.send_receive_frame(&ems::request_regulator()).unwrap();
+/// println!("{:?}", ems::parse_regulator_mode(&result_frame).unwrap());
+/// ```
+pub fn parse_regulator_mode(frame: &Frame) -> Result<u8> {
+    frame.get_item(tags::EMS::REGULATOR_MODE.into())?.as_u8()
+}
+
+/// Returns the raw regulator strategy of the `REGULATOR_STRATEGY` item in the frame
+///
+/// # Arguments
+///
+/// * `frame` - the response frame
+pub fn parse_regulator_strategy(frame: &Frame) -> Result<u8> {
+    frame.get_item(tags::EMS::REGULATOR_STRATEGY.into())?.as_u8()
+}
+
+/// Returns the raw modes of the `SUPPORTED_REGULATOR_MODES` container in the frame
+///
+/// # Arguments
+///
+/// * `frame` - the response frame
+pub fn parse_supported_regulator_modes(frame: &Frame) -> Result<Vec<u8>> {
+    frame.get_item(tags::EMS::SUPPORTED_REGULATOR_MODES.into())?.collect_data::<u8>()
+}
+
+/// Tracks the last seen values of change markers like `SETTINGS_CHANGE_MARKER`, used to detect
+/// stale cached settings
+#[derive(Clone, Default, Debug)]
+pub struct ChangeTracker {
+    /// last seen marker values by request tag
+    markers: HashMap<u32, u32>,
+}
+
+impl ChangeTracker {
+    /// Returns an empty tracker
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores the marker value and returns true if it differs from the last seen value of the
+    /// tag, the first value of a tag is a change
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - the tag of the marker, compared without the response bit
+    /// * `value` - the marker value
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{ems::ChangeTracker, tags};
+    /// let mut tracker = ChangeTracker::new();
+    /// assert!(tracker.changed(tags::EMS::SETTINGS_CHANGE_MARKER.into(), 1));
+    /// assert!(!tracker.changed(tags::EMS::SETTINGS_CHANGE_MARKER.into(), 1));
+    /// ```
+    pub fn changed(&mut self, tag: u32, value: u32) -> bool {
+        self.markers.insert(tags::as_request(tag), value) != Some(value)
+    }
+}
+
+/// ################################################
+///      TEST TEST TEST
+/// ################################################
+
+#[test]
+fn test_idle_period_builder() {
+    use crate::GetItem;
+
+    let idle_period = IdlePeriod::builder().period_type(IdlePeriodType::Discharge).day(6).start(22, 0).end(23, 59).active(true).build().unwrap();
+    assert_eq!(idle_period, IdlePeriod { period_type: IdlePeriodType::Discharge, day: 6, start: (22, 0), end: (23, 59), active: true });
+
+    let frame = set_idle_periods(&[idle_period]);
+    let periods = frame.get_item(tags::EMS::SET_IDLE_PERIODS.into()).unwrap().as_container().unwrap();
+    assert_eq!(periods.len(), 1);
+    assert_eq!(periods[0].get_item_data::<u8>(tags::EMS::IDLE_PERIOD_TYPE.into()).unwrap(), &1);
+    assert_eq!(periods[0].get_item_data::<u8>(tags::EMS::IDLE_PERIOD_DAY.into()).unwrap(), &6);
+    assert_eq!(periods[0].get_item_data::<bool>(tags::EMS::IDLE_PERIOD_ACTIVE.into()).unwrap(), &true);
+    let end = periods[0].get_item(tags::EMS::IDLE_PERIOD_END.into()).unwrap();
+    assert_eq!(end.get_item_data::<u8>(tags::EMS::IDLE_PERIOD_HOUR.into()).unwrap(), &23);
+    assert_eq!(end.get_item_data::<u8>(tags::EMS::IDLE_PERIOD_MINUTE.into()).unwrap(), &59);
+}
+
+#[test]
+fn test_idle_period_builder_invalid() {
+    let err = IdlePeriod::builder().day(7).start(8, 0).end(9, 0).build().unwrap_err();
+    assert_eq!(format!("{}", err), "Invalid value: Idle period day 7 out of range 0-6");
+
+    let err = IdlePeriod::builder().start(8, 0).end(24, 0).build().unwrap_err();
+    assert_eq!(format!("{}", err), "Invalid value: Idle period hour 24 out of range 0-23");
+
+    let err = IdlePeriod::builder().start(8, 60).end(9, 0).build().unwrap_err();
+    assert_eq!(format!("{}", err), "Invalid value: Idle period minute 60 out of range 0-59");
+
+    let err = IdlePeriod::builder().start(9, 0).end(8, 30).build().unwrap_err();
+    assert_eq!(format!("{}", err), "Invalid value: Idle period start 09:00 is not before end 08:30");
+
+    assert!(IdlePeriod::builder().start(8, 0).end(8, 0).build().is_err());
+}
+
+#[test]
+fn test_power_settings() {
+    let settings_items = vec![
+        Item::new_tag(tags::EMS::POWER_LIMITS_USED, true),
+        Item::new_tag(tags::EMS::MAX_CHARGE_POWER, 3000u32),
+        Item::new_tag(tags::EMS::MAX_DISCHARGE_POWER, 4500u32),
+        Item::new_tag(tags::EMS::DISCHARGE_START_POWER, 65u32),
+        Item::new_tag(tags::EMS::POWERSAVE_ENABLED, false),
+        Item::new_tag(tags::EMS::WEATHER_REGULATED_CHARGE_ENABLED, true),
+    ];
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::EMS::GET_POWER_SETTINGS, settings_items.clone()));
+
+    let settings = PowerSettings::from_frame(&frame).unwrap();
+    assert_eq!(settings, PowerSettings {
+        power_limits_used: true,
+        max_charge_power: 3000,
+        max_discharge_power: 4500,
+        discharge_start_power: 65,
+        powersave_enabled: false,
+        weather_regulated_charge_enabled: true,
+    });
+
+    let set_frame = settings.to_set_frame();
+    let set_items = set_frame.get_item(tags::EMS::SET_POWER_SETTINGS.into()).unwrap().as_container().unwrap();
+    assert_eq!(set_items.len(), settings_items.len());
+    for (set_item, settings_item) in set_items.iter().zip(settings_items.iter()) {
+        assert_eq!(set_item.to_bytes().unwrap(), settings_item.to_bytes().unwrap());
+    }
+
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::EMS::GET_POWER_SETTINGS, crate::ErrorCode::AccessDenied));
+    assert!(PowerSettings::from_frame(&frame).is_err());
+    assert!(PowerSettings::from_frame(&PowerSettings::request()).is_err());
+}
+
+#[test]
+fn test_alive_frame() {
+    let frame = alive_frame();
+    assert!(frame.get_item(tags::EMS::ALIVE.into()).unwrap().data.is_none());
+    assert!(frame.is_alive());
+    assert!(!PowerSettings::request().is_alive());
+}
+
+#[test]
+fn test_change_tracker() {
+    let mut tracker = ChangeTracker::new();
+    assert!(tracker.changed(tags::EMS::SETTINGS_CHANGE_MARKER.into(), 7));
+    assert!(!tracker.changed(tags::EMS::SETTINGS_CHANGE_MARKER as u32 | 0x00800000, 7));
+    assert!(tracker.changed(tags::EMS::IDLE_PERIOD_CHANGE_MARKER.into(), 7));
+    assert!(tracker.changed(tags::EMS::SETTINGS_CHANGE_MARKER.into(), 8));
+    assert!(!tracker.changed(tags::EMS::SETTINGS_CHANGE_MARKER.into(), 8));
+    assert!(!tracker.changed(tags::EMS::IDLE_PERIOD_CHANGE_MARKER.into(), 7));
+}
+
+#[test]
+fn test_set_regulator() {
+    let frame = set_regulator_mode(1).without_checksum();
+    assert_eq!(frame.to_bytes().unwrap()[18..], [0x11, 0x02, 0x00, 0x01, 0x03, 0x01, 0x00, 0x01]);
+
+    let frame = set_regulator_strategy(5).without_checksum();
+    assert_eq!(frame.to_bytes().unwrap()[18..], [0x22, 0x02, 0x00, 0x01, 0x03, 0x01, 0x00, 0x05]);
+}
+
+#[test]
+fn test_parse_regulator() {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::EMS::REGULATOR_MODE, 1u8));
+    frame.push_item(Item::new_tag(tags::EMS::REGULATOR_STRATEGY, 0u8));
+    frame.push_item(Item::new_tag(tags::EMS::SUPPORTED_REGULATOR_MODES, vec![
+        Item::new_tag(tags::EMS::REGULATOR_MODE, 0u8),
+        Item::new_tag(tags::EMS::REGULATOR_MODE, 1u8),
+        Item::new_tag(tags::EMS::REGULATOR_MODE, 4u8),
+    ]));
+    assert_eq!(parse_regulator_mode(&frame).unwrap(), 1);
+    assert_eq!(parse_regulator_strategy(&frame).unwrap(), 0);
+    assert_eq!(parse_supported_regulator_modes(&frame).unwrap(), vec![0, 1, 4]);
+
+    assert!(parse_regulator_mode(&request_regulator()).is_err());
+    assert!(request_regulator().get_item(tags::EMS::REGULATOR_MODE.into()).unwrap().data.is_none());
+}
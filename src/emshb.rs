@@ -0,0 +1,186 @@
+use anyhow::Result;
+
+use crate::{tags, Frame, GetItem, Item};
+
+/// Wallbox entry of the home bus data
+#[derive(Copy, Clone, Default, PartialEq, Debug)]
+pub struct HomeBusWallbox {
+    /// index of the wallbox
+    pub index: Option<u32>,
+
+    /// power per phase in watt
+    pub power: [f64; 3],
+
+    /// solar power per phase in watt
+    pub solar_power: [f64; 3],
+
+    /// true for the active phases
+    pub active_phases: [bool; 3],
+
+    /// alive flag of the wallbox
+    pub alive: Option<bool>,
+}
+
+/// Telemetry of the `HB_DATA` container, the vectors contain the present entries in tag order
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct HomeBusData {
+    /// version of the data block
+    pub version: Option<u32>,
+
+    /// power of the battery strings in watt
+    pub battery_strings: Vec<f64>,
+
+    /// power of the load meters in watt
+    pub load_meters: Vec<f64>,
+
+    /// power of the AC phases in watt
+    pub ac_phases: Vec<f64>,
+
+    /// consumption of the phases in watt
+    pub consumption_phases: Vec<f64>,
+
+    /// power of the PV strings in watt
+    pub pv_strings: Vec<f64>,
+
+    /// state of charge in percent
+    pub soc: Option<f64>,
+
+    /// system status
+    pub sys_status: Option<u32>,
+
+    /// wallbox entries
+    pub wallboxes: Vec<HomeBusWallbox>,
+}
+
+impl HomeBusData {
+    /// Returns home bus data parsed from the `HB_DATA` container of the frame, unknown tags are ignored
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - the response frame
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rscp::emshb::{self, HomeBusData};
+    /// let mut c = rscp::Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    /// c.connect("energy.storage.local", None).unwrap();
+    /// let data = HomeBusData::from_frame(&c.send_receive_frame(&emshb::request_data()).unwrap()).unwrap();
+    /// println!("PV strings: {:?} W", data.pv_strings);
+    /// ```
+    pub fn from_frame(frame: &Frame) -> Result<Self> {
+        let mut data = Self::default();
+        for item in frame.get_item(tags::EMSHB::HB_DATA.into())?.as_container()? {
+            match tags::EMSHB::from(item.tag) {
+                tags::EMSHB::PARAM_VERSION => data.version = Some(item.as_f64_lossy()? as u32),
+                tags::EMSHB::PARAM_BAT_S1 | tags::EMSHB::PARAM_BAT_S2 | tags::EMSHB::PARAM_BAT_S3 => data.battery_strings.push(item.as_f64_lossy()?),
+                tags::EMSHB::PARAM_LM1 | tags::EMSHB::PARAM_LM2 | tags::EMSHB::PARAM_LM3 => data.load_meters.push(item.as_f64_lossy()?),
+                tags::EMSHB::PARAM_AC_L1 | tags::EMSHB::PARAM_AC_L2 | tags::EMSHB::PARAM_AC_L3 => data.ac_phases.push(item.as_f64_lossy()?),
+                tags::EMSHB::PARAM_C_L1 | tags::EMSHB::PARAM_C_L2 | tags::EMSHB::PARAM_C_L3 => data.consumption_phases.push(item.as_f64_lossy()?),
+                tags::EMSHB::PARAM_PV_S1 | tags::EMSHB::PARAM_PV_S2 | tags::EMSHB::PARAM_PV_S3 => data.pv_strings.push(item.as_f64_lossy()?),
+                tags::EMSHB::PARAM_SOC => data.soc = Some(item.as_f64_lossy()?),
+                tags::EMSHB::PARAM_SYS_STATUS => data.sys_status = Some(item.as_f64_lossy()? as u32),
+                tags::EMSHB::PARAM_WB => data.wallboxes.push(parse_wallbox(item)?),
+                _ => (),
+            }
+        }
+        Ok(data)
+    }
+}
+
+/// Returns frame requesting the home bus data
+pub fn request_data() -> Frame {
+    let mut frame = Frame::new();
+    frame.push_item(Item::from_tag(tags::EMSHB::HB_DATA));
+    frame
+}
+
+/// Returns wallbox parsed from `PARAM_WB` container
+///
+/// # Arguments
+///
+/// * `container` - the wallbox container item
+fn parse_wallbox(container: &Item) -> Result<HomeBusWallbox> {
+    let mut wallbox = HomeBusWallbox::default();
+    for item in container.as_container()? {
+        match tags::EMSHB::from(item.tag) {
+            tags::EMSHB::PARAM_WB_INDEX => wallbox.index = Some(item.as_f64_lossy()? as u32),
+            tags::EMSHB::PARAM_WB_L1 => wallbox.power[0] = item.as_f64_lossy()?,
+            tags::EMSHB::PARAM_WB_L2 => wallbox.power[1] = item.as_f64_lossy()?,
+            tags::EMSHB::PARAM_WB_L3 => wallbox.power[2] = item.as_f64_lossy()?,
+            tags::EMSHB::PARAM_WB_SOLAR_L1 => wallbox.solar_power[0] = item.as_f64_lossy()?,
+            tags::EMSHB::PARAM_WB_SOLAR_L2 => wallbox.solar_power[1] = item.as_f64_lossy()?,
+            tags::EMSHB::PARAM_WB_SOLAR_L3 => wallbox.solar_power[2] = item.as_f64_lossy()?,
+            tags::EMSHB::PARAM_WB_L1_Active => wallbox.active_phases[0] = item.as_bool()?,
+            tags::EMSHB::PARAM_WB_L2_Active => wallbox.active_phases[1] = item.as_bool()?,
+            tags::EMSHB::PARAM_WB_L3_Active => wallbox.active_phases[2] = item.as_bool()?,
+            tags::EMSHB::PARAM_WB_ALIVE_FLAG => wallbox.alive = Some(item.as_bool()?),
+            _ => (),
+        }
+    }
+    Ok(wallbox)
+}
+
+/// ################################################
+///      TEST TEST TEST
+/// ################################################
+
+#[test]
+fn test_request_data() {
+    let frame = request_data();
+    assert!(frame.get_item(tags::EMSHB::HB_DATA.into()).unwrap().data.is_none());
+}
+
+#[test]
+fn test_home_bus_data_from_frame() {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::EMSHB::HB_DATA, vec![
+        Item::new_tag(tags::EMSHB::PARAM_VERSION, 2u8),
+        Item::new_tag(tags::EMSHB::PARAM_BAT_S1, -1200i32),
+        Item::new_tag(tags::EMSHB::PARAM_BAT_S2, -800i32),
+        Item::new_tag(tags::EMSHB::PARAM_LM1, 150i32),
+        Item::new_tag(tags::EMSHB::PARAM_AC_L1, 400.5f32),
+        Item::new_tag(tags::EMSHB::PARAM_AC_L2, 410.0f32),
+        Item::new_tag(tags::EMSHB::PARAM_AC_L3, 420.0f32),
+        Item::new_tag(tags::EMSHB::PARAM_C_L1, 300i32),
+        Item::new_tag(tags::EMSHB::PARAM_C_L2, 200i32),
+        Item::new_tag(tags::EMSHB::PARAM_C_L3, 100i32),
+        Item::new_tag(tags::EMSHB::PARAM_SOC, 85.5f32),
+        Item::new_tag(tags::EMSHB::PARAM_SYS_STATUS, 3u32),
+        Item::new_tag(tags::EMSHB::PARAM_WB, vec![
+            Item::new_tag(tags::EMSHB::PARAM_WB_INDEX, 0u8),
+            Item::new_tag(tags::EMSHB::PARAM_WB_L1, 2300i32),
+            Item::new_tag(tags::EMSHB::PARAM_WB_L2, 2300i32),
+            Item::new_tag(tags::EMSHB::PARAM_WB_SOLAR_L1, 1000i32),
+            Item::new(tags::EMSHB::PARAM_WB_L1_Active.into(), true),
+            Item::new(tags::EMSHB::PARAM_WB_L2_Active.into(), true),
+            Item::new_tag(tags::EMSHB::PARAM_WB_ALIVE_FLAG, true),
+        ]),
+        Item::new_tag(tags::EMSHB::PARAM_PV_S1, 2500i32),
+        Item::new_tag(tags::EMSHB::PARAM_PV_S2, 1800i32),
+    ]));
+
+    assert_eq!(HomeBusData::from_frame(&frame).unwrap(), HomeBusData {
+        version: Some(2),
+        battery_strings: vec![-1200.0, -800.0],
+        load_meters: vec![150.0],
+        ac_phases: vec![400.5, 410.0, 420.0],
+        consumption_phases: vec![300.0, 200.0, 100.0],
+        pv_strings: vec![2500.0, 1800.0],
+        soc: Some(85.5),
+        sys_status: Some(3),
+        wallboxes: vec![HomeBusWallbox {
+            index: Some(0),
+            power: [2300.0, 2300.0, 0.0],
+            solar_power: [1000.0, 0.0, 0.0],
+            active_phases: [true, true, false],
+            alive: Some(true),
+        }],
+    });
+
+    assert!(HomeBusData::from_frame(&request_data()).is_err());
+
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::EMSHB::HB_DATA, vec![Item::new_tag(tags::EMSHB::PARAM_SOC, "85".to_string())]));
+    assert_eq!(HomeBusData::from_frame(&frame).unwrap_err().to_string(), "Type mismatch, expected Double64 got String");
+}
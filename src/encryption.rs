@@ -5,6 +5,9 @@ use simple_rijndael::paddings::ZeroPadding;
 /// size of encyption blocks
 pub const BLOCK_SIZE: usize = 32;
 
+/// iv at the start of a connection
+const INITIAL_IV: [u8; BLOCK_SIZE] = [0xff; BLOCK_SIZE];
+
 /// Encryption container
 pub struct RscpEncryption {
     /// Rijndael Key
@@ -33,11 +36,42 @@ impl RscpEncryption {
 
         Self {
             key: key,
-            dec_iv: [0xff; BLOCK_SIZE],
-            enc_iv: [0xff; BLOCK_SIZE],
+            dec_iv: INITIAL_IV,
+            enc_iv: INITIAL_IV,
         }
     }
 
+    /// Returns the current encryption iv, the last encrypted block of the connection
+    pub fn enc_iv(&self) -> [u8; BLOCK_SIZE] {
+        self.enc_iv
+    }
+
+    /// Returns the current decryption iv, the last received block of the connection
+    pub fn dec_iv(&self) -> [u8; BLOCK_SIZE] {
+        self.dec_iv
+    }
+
+    /// Sets the iv state, e.g. to resume a proxied connection mid-session
+    ///
+    /// The ivs chain all messages of a connection and must start at the initial state for a
+    /// new connection. Reusing an iv state for a different connection or setting ivs known to a
+    /// third party breaks the confidentiality of the CBC encryption, so only restore state
+    /// previously read from the same session and keep it as secret as the key.
+    ///
+    /// # Arguments
+    ///
+    /// * `enc_iv` - the encryption iv
+    /// * `dec_iv` - the decryption iv
+    pub fn set_ivs(&mut self, enc_iv: [u8; BLOCK_SIZE], dec_iv: [u8; BLOCK_SIZE]) {
+        self.enc_iv = enc_iv;
+        self.dec_iv = dec_iv;
+    }
+
+    /// Resets the ivs to the initial state of a new connection
+    pub fn reset_ivs(&mut self) {
+        self.set_ivs(INITIAL_IV, INITIAL_IV);
+    }
+
     /// encrypts data using key and enc iv and saves new iv
     ///
     /// # Arguments
@@ -115,3 +149,24 @@ fn test_decryption() {
         0x8f, 0x64, 0x0f, 0xf4, 0x19, 0x62, 0x82, 0x84, 0x34, 0xe2, 0x00, 0x9a, 0xcc, 0x13, 0x89, 0xfd]).unwrap();
     assert_eq!(res, "000111222333444555666777888999000\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0".as_bytes(), "Test 33 byte decryption");
 }
+
+#[test]
+fn test_iv_state() {
+    let mut enc = RscpEncryption::new("RSCP_KEY");
+    assert_eq!(enc.enc_iv(), [0xff; BLOCK_SIZE]);
+    assert_eq!(enc.dec_iv(), [0xff; BLOCK_SIZE]);
+
+    enc.set_ivs([0x01; BLOCK_SIZE], [0x02; BLOCK_SIZE]);
+    assert_eq!(enc.enc_iv(), [0x01; BLOCK_SIZE]);
+    assert_eq!(enc.dec_iv(), [0x02; BLOCK_SIZE]);
+    enc.reset_ivs();
+    assert_eq!(enc.enc_iv(), [0xff; BLOCK_SIZE]);
+    assert_eq!(enc.dec_iv(), [0xff; BLOCK_SIZE]);
+
+    // a resumed session continues the chain of the original
+    let mut enc = RscpEncryption::new("RSCP_KEY");
+    enc.encrypt(vec![0x01; BLOCK_SIZE]).unwrap();
+    let mut resumed = RscpEncryption::new("RSCP_KEY");
+    resumed.set_ivs(enc.enc_iv(), enc.dec_iv());
+    assert_eq!(resumed.encrypt(vec![0x02; BLOCK_SIZE]).unwrap(), enc.encrypt(vec![0x02; BLOCK_SIZE]).unwrap());
+}
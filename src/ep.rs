@@ -0,0 +1,109 @@
+use anyhow::Result;
+
+use crate::{tags, Frame, GetItem, Item};
+
+/// Emergency power states of the device
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct EpStatus {
+    /// device is ready to switch between grid and island mode
+    pub ready_for_switch: bool,
+
+    /// device is connected to the grid
+    pub grid_connected: bool,
+
+    /// device runs in island mode
+    pub island_grid: bool,
+
+    /// device is in an invalid state
+    pub invalid_state: bool,
+
+    /// emergency power is possible
+    pub possible: bool,
+}
+
+/// Returns frame to switch the device to island mode
+pub fn switch_to_island() -> Frame {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::EP::SWITCH_TO_ISLAND, true));
+    frame
+}
+
+/// Returns frame to switch the device back to the grid
+pub fn switch_to_grid() -> Frame {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::EP::SWITCH_TO_GRID, true));
+    frame
+}
+
+/// Returns frame requesting the emergency power states
+pub fn request_status() -> Frame {
+    let mut frame = Frame::new();
+    frame.push_item(Item::from_tag(tags::EP::IS_READY_FOR_SWITCH));
+    frame.push_item(Item::from_tag(tags::EP::IS_GRID_CONNECTED));
+    frame.push_item(Item::from_tag(tags::EP::IS_ISLAND_GRID));
+    frame.push_item(Item::from_tag(tags::EP::IS_INVALID_STATE));
+    frame.push_item(Item::from_tag(tags::EP::IS_POSSIBLE));
+    frame
+}
+
+/// Returns the emergency power states of the response frame
+///
+/// # Arguments
+///
+/// * `frame` - the response frame
+///
+/// # Examples
+///
+/// ```no_run
+/// use rscp::ep;
+/// let mut c = rscp::Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+/// c.connect("energy.storage.local", None).unwrap();
+/// let result_frame = c.send_receive_frame(&ep::request_status()).unwrap();
+/// let status = ep::parse_status(&result_frame).unwrap();
+/// if status.ready_for_switch {
+///     c.send_receive_frame(&ep::switch_to_island()).unwrap();
+/// }
+/// ```
+pub fn parse_status(frame: &Frame) -> Result<EpStatus> {
+    Ok(EpStatus {
+        ready_for_switch: frame.get_item(tags::EP::IS_READY_FOR_SWITCH.into())?.as_bool()?,
+        grid_connected: frame.get_item(tags::EP::IS_GRID_CONNECTED.into())?.as_bool()?,
+        island_grid: frame.get_item(tags::EP::IS_ISLAND_GRID.into())?.as_bool()?,
+        invalid_state: frame.get_item(tags::EP::IS_INVALID_STATE.into())?.as_bool()?,
+        possible: frame.get_item(tags::EP::IS_POSSIBLE.into())?.as_bool()?,
+    })
+}
+
+/// ################################################
+///      TEST TEST TEST
+/// ################################################
+
+#[test]
+fn test_switch() {
+    let frame = switch_to_island();
+    assert_eq!(frame.get_item_data::<bool>(tags::EP::SWITCH_TO_ISLAND.into()).unwrap(), &true);
+    assert!(frame.get_item(tags::EP::SWITCH_TO_GRID.into()).is_err());
+
+    let frame = switch_to_grid();
+    assert_eq!(frame.get_item_data::<bool>(tags::EP::SWITCH_TO_GRID.into()).unwrap(), &true);
+    assert!(frame.get_item(tags::EP::SWITCH_TO_ISLAND.into()).is_err());
+}
+
+#[test]
+fn test_parse_status() {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::EP::IS_READY_FOR_SWITCH, true));
+    frame.push_item(Item::new_tag(tags::EP::IS_GRID_CONNECTED, true));
+    frame.push_item(Item::new_tag(tags::EP::IS_ISLAND_GRID, false));
+    frame.push_item(Item::new_tag(tags::EP::IS_INVALID_STATE, false));
+    frame.push_item(Item::new_tag(tags::EP::IS_POSSIBLE, true));
+
+    assert_eq!(parse_status(&frame).unwrap(), EpStatus {
+        ready_for_switch: true,
+        grid_connected: true,
+        island_grid: false,
+        invalid_state: false,
+        possible: true,
+    });
+    assert!(parse_status(&request_status()).is_err());
+}
@@ -1,3 +1,6 @@
+use crate::item::DataType;
+use crate::Item;
+
 macro_rules! error_code_ext {
     (
         $(#[$($attrs:tt)*])*
@@ -43,6 +46,25 @@ error_code_ext! {
     }
 }
 
+impl ErrorCode {
+    /// Returns the error code if the item contains error data, `None` for other data
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - the response item
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{tags, ErrorCode, Item};
+    /// let item = Item::new(tags::EMS::BAT_SOC.into(), ErrorCode::AccessDenied);
+    /// assert_eq!(ErrorCode::from_item(&item), Some(ErrorCode::AccessDenied));
+    /// ```
+    pub fn from_item(item: &Item) -> Option<ErrorCode> {
+        item.data.as_ref().and_then(|data| data.downcast_ref::<ErrorCode>()).copied()
+    }
+}
+
 /// Errors pubished by the package.
 #[derive(Debug)] // Allow the use of "{:?}" format specifier
 pub enum Errors {
@@ -52,8 +74,38 @@ pub enum Errors {
     ReceiveNothing,
     /// Authentication failed.
     AuthFailed,
+    /// Authentication response could not be decrypted, the RSCP key is wrong.
+    KeyInvalid,
     /// If not connected.
     NotConnected,
+    /// Error while receiving data from connection.
+    Receive(String),
+    /// Request was cancelled.
+    Cancelled,
+    /// Item data has not the requested type.
+    TypeMismatch {
+        expected: DataType,
+        actual: DataType,
+    },
+    /// Item has no data, e.g. the item of a request.
+    NoData,
+    /// Value is not valid for the request.
+    InvalidValue(String),
+    /// Connection through the proxy failed.
+    Proxy(String),
+    /// Received data does not start with the RSCP magic id, e.g. not an RSCP port.
+    InvalidMagic {
+        got: u16,
+    },
+    /// Received frame has an unsupported protocol version.
+    UnsupportedVersion {
+        got: u8,
+    },
+    /// Checksum of the received frame does not match the data, e.g. decrypted with a wrong key.
+    ChecksumMismatch {
+        got: u32,
+        expected: u32,
+    },
 }
 
 impl std::error::Error for Errors {}
@@ -64,7 +116,17 @@ impl std::fmt::Display for Errors {
             Errors::Parse(ref msg) => write!(f, "Frame parse error: {}", msg),
             Errors::ReceiveNothing => write!(f, "Receive nothing"),
             Errors::AuthFailed => write!(f, "Authentication failed"),
+            Errors::KeyInvalid => write!(f, "Invalid RSCP key"),
             Errors::NotConnected => write!(f, "Not Connected"),
+            Errors::Receive(ref msg) => write!(f, "Receive error: {}", msg),
+            Errors::Cancelled => write!(f, "Cancelled"),
+            Errors::TypeMismatch { expected, actual } => write!(f, "Type mismatch, expected {:?} got {:?}", expected, actual),
+            Errors::NoData => write!(f, "Item has no data"),
+            Errors::InvalidValue(ref msg) => write!(f, "Invalid value: {}", msg),
+            Errors::Proxy(ref msg) => write!(f, "Proxy error: {}", msg),
+            Errors::InvalidMagic { got } => write!(f, "Invalid magic header, got {:#06x}", got),
+            Errors::UnsupportedVersion { got } => write!(f, "Unsupported protocol version, got {}", got),
+            Errors::ChecksumMismatch { got, expected } => write!(f, "CRC checksum mismatch, got {:#010x} expected {:#010x}", got, expected),
         }
     }
 }
@@ -78,7 +140,31 @@ fn test_error_display_impl() {
     assert_eq!(format!("{}", Errors::Parse("test".to_string())), "Frame parse error: test");
     assert_eq!(format!("{}", Errors::ReceiveNothing), "Receive nothing");
     assert_eq!(format!("{}", Errors::AuthFailed), "Authentication failed");
+    assert_eq!(format!("{}", Errors::KeyInvalid), "Invalid RSCP key");
     assert_eq!(format!("{}", Errors::NotConnected), "Not Connected");
+    assert_eq!(format!("{}", Errors::Receive("test".to_string())), "Receive error: test");
+    assert_eq!(format!("{}", Errors::Cancelled), "Cancelled");
+    assert_eq!(format!("{}", Errors::TypeMismatch { expected: DataType::String, actual: DataType::None }), "Type mismatch, expected String got None");
+    assert_eq!(format!("{}", Errors::NoData), "Item has no data");
+    assert_eq!(format!("{}", Errors::InvalidValue("test".to_string())), "Invalid value: test");
+    assert_eq!(format!("{}", Errors::Proxy("test".to_string())), "Proxy error: test");
+    assert_eq!(format!("{}", Errors::InvalidMagic { got: 0x4854 }), "Invalid magic header, got 0x4854");
+    assert_eq!(format!("{}", Errors::UnsupportedVersion { got: 18 }), "Unsupported protocol version, got 18");
+    assert_eq!(format!("{}", Errors::ChecksumMismatch { got: 0x12345678, expected: 0x3c84fafe }), "CRC checksum mismatch, got 0x12345678 expected 0x3c84fafe");
+}
+
+#[test]
+fn test_error_code_from_item() {
+    use crate::tags;
+
+    let item = Item::new_tag(tags::EMS::BAT_SOC, ErrorCode::NotAvailable);
+    assert_eq!(ErrorCode::from_item(&item), Some(ErrorCode::NotAvailable));
+
+    let item = Item::new_tag(tags::EMS::BAT_SOC, 80u8);
+    assert_eq!(ErrorCode::from_item(&item), None);
+
+    let item = Item::from_tag(tags::EMS::BAT_SOC);
+    assert_eq!(ErrorCode::from_item(&item), None);
 }
 
 #[test]
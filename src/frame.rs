@@ -1,15 +1,17 @@
 
-use anyhow::{bail, Result};
+use anyhow::{anyhow, bail, Result};
 use chrono::{DateTime, Utc};
-use crc::{Crc, CRC_32_ISO_HDLC};
-use std::any::Any;
+use crc::{Crc, Digest, CRC_32_ISO_HDLC};
+use log::trace;
+use std::any::{Any, TypeId};
+use std::cell::RefCell;
 use std::fmt::Debug;
 use std::io::Cursor;
 use std::io::Write;
 
-use crate::item::{get_data_length, read_timestamp, write_data, write_timestamp, DataType};
+use crate::item::{get_data_length, get_data_type, get_type_id_data_type, read_timestamp, trace_item, validate_types, write_data, write_timestamp, DataType, ITEM_HEADER_SIZE, TAG_MASK};
 use crate::read_ext::ReadExt;
-use crate::{Errors, GetItem, Item};
+use crate::{ContainerBuilder, ErrorCode, Errors, GetItem, Item, ItemContainer};
 
 /// the protocol magic id for rscp frame
 const MAGIC_ID: u16 = 0xE3DC;
@@ -17,22 +19,168 @@ const MAGIC_ID: u16 = 0xE3DC;
 /// version of protocol
 const PROTOCOL_VERSION: u8 = 0x01;
 
+/// protocol versions the parser supports, the frame layout of version 1 is used for all versions
+const SUPPORTED_PROTOCOL_VERSIONS: [u8; 2] = [PROTOCOL_VERSION, 0x02];
+
 /// bitmask for protocol
 const PROTOCOL_VERSION_MASK: u8 = 0x0F;
 
 /// with checksum flag of frame
 const WITH_CHECKSUM: u8 = 0x10;
 
+/// size of frame header - magic: 2, control: 2, timestamp: 12, length: 2
+const FRAME_HEADER_SIZE: usize = 18;
+
+/// size of frame checksum
+const CHECKSUM_SIZE: usize = 4;
+
+/// checksum algorithm of the frame
+static RSCP_CRC: Crc<u32> = Crc::<u32>::new(&CRC_32_ISO_HDLC);
+
+/// source of the timestamp of new frames
+type Clock = Box<dyn Fn() -> DateTime<Utc>>;
+
+thread_local! {
+    /// clock overriding `Utc::now` for frames created on the thread
+    static CLOCK: RefCell<Option<Clock>> = RefCell::new(None);
+}
+
 /// RSCP data frame
 pub struct Frame {
     /// true if frame contains checksum
     pub with_checksum: bool,
 
+    /// the protocol version of the frame
+    protocol_version: u8,
+
     /// the timestamp of the frame
     pub time_stamp: DateTime<Utc>,
 
     /// contains data items
     pub items: Option<Box<dyn Any>>,
+
+    /// data length declared in the header of a parsed frame, not updated on changes of the items,
+    /// `None` for frames created locally
+    data_length: Option<u16>,
+}
+
+/// Difference between the items of two frames, the path contains the tags from the top-level
+/// item down to the differing item
+#[derive(Clone, Debug)]
+pub enum Difference {
+    /// item only exists in the other frame
+    Added { path: Vec<u32>, item: Item },
+    /// item only exists in this frame
+    Removed { path: Vec<u32>, item: Item },
+    /// item data differs
+    Changed { path: Vec<u32>, old: Item, new: Item },
+}
+
+/// Pre-serialized RSCP data frame, for requests which are sent repeatedly without changes
+pub struct FrozenFrame {
+    /// the frozen frame
+    frame: Frame,
+
+    /// the serialized frame
+    bytes: Vec<u8>,
+}
+
+impl FrozenFrame {
+    /// Returns the frozen frame
+    pub fn frame(&self) -> &Frame {
+        &self.frame
+    }
+
+    /// Returns the serialized frame
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// Returns the frame, the serialized bytes are dropped
+    pub fn into_frame(self) -> Frame {
+        self.frame
+    }
+}
+
+/// Incremental parser of a frame received in parts, each top-level item is passed on as soon as it
+/// is complete and its data is dropped
+pub(crate) struct FrameReader {
+    /// received data which is not parsed yet
+    buffer: Vec<u8>,
+
+    /// checksum flag of the frame, known once the header is complete
+    with_checksum: bool,
+
+    /// remaining data length of the frame, known once the header is complete
+    remaining: Option<u16>,
+
+    /// checksum of the parsed data
+    digest: Digest<'static, u32>,
+}
+
+impl FrameReader {
+    /// Returns a parser waiting for the frame header
+    pub(crate) fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            with_checksum: false,
+            remaining: None,
+            digest: RSCP_CRC.digest(),
+        }
+    }
+
+    /// Appends the data and passes each complete top-level item to the callback, returns true once
+    /// the frame is complete
+    ///
+    /// A checksum mismatch is an error, data after the frame is ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - the next part of the frame data
+    /// * `on_item` - callback for each top-level item
+    pub(crate) fn push<F: FnMut(Item)>(&mut self, data: &[u8], mut on_item: F) -> Result<bool> {
+        self.buffer.extend_from_slice(data);
+        if self.remaining.is_none() {
+            if self.buffer.len() < FRAME_HEADER_SIZE {
+                return Ok(false);
+            }
+            let (_, with_checksum, _, length) = read_frame_header(&mut Cursor::new(&self.buffer[..]))?;
+            self.digest.update(&self.buffer[..FRAME_HEADER_SIZE]);
+            self.buffer.drain(..FRAME_HEADER_SIZE);
+            self.with_checksum = with_checksum;
+            self.remaining = Some(length);
+        }
+
+        while let Some(mut remaining) = self.remaining.filter(|remaining| *remaining > 0) {
+            if self.buffer.len() < ITEM_HEADER_SIZE as usize {
+                return Ok(false);
+            }
+            // an item exceeding the frame is reported by the item parser without waiting for its data
+            let item_size = ITEM_HEADER_SIZE as usize + u16::from_le_bytes([self.buffer[5], self.buffer[6]]) as usize;
+            if item_size <= remaining as usize && self.buffer.len() < item_size {
+                return Ok(false);
+            }
+            let item = Item::read_bytes(&mut Cursor::new(&self.buffer[..]), &mut remaining)?;
+            self.digest.update(&self.buffer[..item_size]);
+            self.buffer.drain(..item_size);
+            self.remaining = Some(remaining);
+            on_item(item);
+        }
+
+        if self.with_checksum {
+            if self.buffer.len() < CHECKSUM_SIZE {
+                return Ok(false);
+            }
+            let cksum = u32::from_le_bytes([self.buffer[0], self.buffer[1], self.buffer[2], self.buffer[3]]);
+            let sum = std::mem::replace(&mut self.digest, RSCP_CRC.digest()).finalize();
+            if cksum != sum {
+                bail!(Errors::ChecksumMismatch { got: cksum, expected: sum })
+            }
+            self.with_checksum = false;
+        }
+        self.buffer.clear();
+        Ok(true)
+    }
 }
 
 impl Frame {
@@ -45,11 +193,142 @@ impl Frame {
     /// let frame = Frame::new();
     /// ```
     pub fn new() -> Self {
+        Self::new_with_checksum(true)
+    }
+
+    /// Returns a frame with or without checksum
+    ///
+    /// # Arguments
+    ///
+    /// * `with_checksum` - append a checksum to the serialized frame
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::Frame;
+    /// let frame = Frame::new_with_checksum(false);
+    /// assert!(!frame.with_checksum);
+    /// ```
+    pub fn new_with_checksum(with_checksum: bool) -> Self {
         Self {
-            with_checksum: true,
-            time_stamp: Utc::now(),
+            with_checksum,
+            protocol_version: PROTOCOL_VERSION,
+            time_stamp: CLOCK.with(|clock| clock.borrow().as_ref().map_or_else(Utc::now, |clock| clock())),
             items: Some(Box::new(Vec::new() as Vec<Item>)),
+            data_length: None,
+        }
+    }
+
+    /// Returns a frame requesting the tags, each as item without data
+    ///
+    /// # Arguments
+    ///
+    /// * `tags` - the requested tags
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{tags, Frame};
+    /// let frame = Frame::request_tags(&[tags::EMS::POWER_PV.into(), tags::EMS::POWER_BAT.into(), tags::EMS::BAT_SOC.into()]);
+    /// ```
+    pub fn request_tags(tags: &[u32]) -> Self {
+        let mut frame = Self::new();
+        for tag in tags {
+            frame.push_item(Item { tag: *tag, data: None });
         }
+        frame
+    }
+
+    /// Sets the clock providing the timestamp of frames created on the current thread, `None`
+    /// restores `Utc::now`
+    ///
+    /// # Arguments
+    ///
+    /// * `clock` - function returning the timestamp
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::{DateTime, Utc};
+    /// use rscp::Frame;
+    /// let time_stamp = DateTime::<Utc>::from_timestamp(1700000000, 0).unwrap();
+    /// Frame::set_clock(Some(Box::new(move || time_stamp)));
+    /// assert_eq!(Frame::new().time_stamp, time_stamp);
+    /// Frame::set_clock(None);
+    /// ```
+    pub fn set_clock(clock: Option<Box<dyn Fn() -> DateTime<Utc>>>) {
+        CLOCK.with(|current| *current.borrow_mut() = clock);
+    }
+
+    /// Returns the data length declared in the header of a parsed frame, `None` for frames created
+    /// locally, the length is not updated on changes of the items
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::Frame;
+    /// assert_eq!(Frame::new().data_length(), None);
+    /// ```
+    pub fn data_length(&self) -> Option<u16> {
+        self.data_length
+    }
+
+    /// Returns the protocol version of the frame, for parsed frames the version sent by the device
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::Frame;
+    /// assert_eq!(Frame::new().protocol_version(), 0x01);
+    /// ```
+    pub fn protocol_version(&self) -> u8 {
+        self.protocol_version
+    }
+
+    /// Sets the protocol version of the frame, only the lower 4 bits are used
+    ///
+    /// # Arguments
+    ///
+    /// * `protocol_version` - the protocol version
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::Frame;
+    /// let mut frame = Frame::new();
+    /// frame.set_protocol_version(0x02);
+    /// assert_eq!(frame.protocol_version(), 0x02);
+    /// ```
+    pub fn set_protocol_version(&mut self, protocol_version: u8) {
+        self.protocol_version = protocol_version & PROTOCOL_VERSION_MASK;
+    }
+
+    /// Returns the frame with checksum enabled
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::Frame;
+    /// let frame = Frame::new().without_checksum().with_checksum();
+    /// assert!(frame.with_checksum);
+    /// ```
+    pub fn with_checksum(mut self) -> Self {
+        self.with_checksum = true;
+        self
+    }
+
+    /// Returns the frame with checksum disabled
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::Frame;
+    /// let frame = Frame::new().without_checksum();
+    /// assert!(!frame.with_checksum);
+    /// ```
+    pub fn without_checksum(mut self) -> Self {
+        self.with_checksum = false;
+        self
     }
 
     /// Appends data item to current frame
@@ -71,6 +350,243 @@ impl Frame {
         items_vector.push(item);
     }
 
+    /// Appends a container item with the sub items added by the closure, see `Item::container`
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - the tag of the container
+    /// * `build` - closure adding the sub items to the builder
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{tags, Item, Frame};
+    /// let mut frame = Frame::new();
+    /// frame.push_container(tags::SE::EP_RESERVE.into(), |c| {
+    ///     c.item(Item::new(tags::SE::PARAM_INDEX.into(), 0u8));
+    /// });
+    /// ```
+    pub fn push_container(&mut self, tag: u32, build: impl FnOnce(&mut ContainerBuilder)) {
+        self.push_item(Item::container(tag, build));
+    }
+
+    /// Moves the items of the other frame to the end of the current frame, the order is preserved
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - the frame to take the items from
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{tags, Item, Frame};
+    /// let mut info_frame = Frame::new();
+    /// info_frame.push_item(Item { tag: tags::INFO::SERIAL_NUMBER.into(), data: None } );
+    /// let mut ems_frame = Frame::new();
+    /// ems_frame.push_item(Item { tag: tags::EMS::POWER_PV.into(), data: None } );
+    /// info_frame.append(ems_frame);
+    /// ```
+    pub fn append(&mut self, mut other: Frame) {
+        if let Some(mut other_items) = other.items.take().and_then(|items| items.downcast::<Vec<Item>>().ok()) {
+            let items_box = self.items.as_mut().unwrap();
+            let items_vector = items_box.downcast_mut::<Vec<Item>>().unwrap();
+            items_vector.append(&mut other_items);
+        }
+    }
+
+    /// Returns the items of the frame, consuming the frame
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{tags, Item, Frame};
+    /// let mut frame = Frame::new();
+    /// frame.push_item(Item::new(tags::EMS::POWER_PV.into(), 1200i32));
+    /// let items: Vec<Item> = frame.into_items();
+    /// ```
+    pub fn into_items(mut self) -> Vec<Item> {
+        self.items.take().and_then(|items| items.downcast::<Vec<Item>>().ok()).map_or_else(Vec::new, |items| *items)
+    }
+
+    /// Returns the response item or error code for each item of the request frame
+    ///
+    /// Tags are compared without the response bit. If the response contains no item
+    /// for a requested tag, `ErrorCode::NotHandled` is reported.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - the request frame sent to the device
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{tags, Item, Frame};
+    /// let mut request = Frame::new();
+    /// request.push_item(Item { tag: tags::INFO::SERIAL_NUMBER.into(), data: None } );
+    /// let mut response = Frame::new();
+    /// response.push_item(Item::new(tags::INFO::SERIAL_NUMBER.into(), "serial".to_string()));
+    /// for (tag, result) in response.response_for(&request) {
+    ///     println!("{}: {:?}", tag, result);
+    /// }
+    /// ```
+    pub fn response_for(&self, request: &Frame) -> Vec<(u32, std::result::Result<&Item, ErrorCode>)> {
+        let items = self.get_data::<Vec<Item>>().unwrap();
+        let mut results = Vec::new();
+        for request_item in request.get_data::<Vec<Item>>().unwrap() {
+            let tag = request_item.tag & TAG_MASK;
+            let result = match items.iter().find(|item| item.tag & TAG_MASK == tag) {
+                Some(item) => match ErrorCode::from_item(item) {
+                    Some(error_code) => Err(error_code),
+                    None => Ok(item),
+                },
+                None => Err(ErrorCode::NotHandled),
+            };
+            results.push((tag, result));
+        }
+        results
+    }
+
+    /// Returns each requested tag paired with the first item of the frame with that tag
+    ///
+    /// Tags are compared without the response bit, missing tags are paired with `None`.
+    ///
+    /// # Arguments
+    ///
+    /// * `tags` - the requested tags
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{tags, Item, Frame};
+    /// let mut frame = Frame::new();
+    /// frame.push_item(Item::new(tags::EMS::POWER_PV.into(), 1200i32));
+    /// let values = frame.get_many(&[tags::EMS::POWER_PV.into(), tags::EMS::POWER_BAT.into()]);
+    /// assert!(values[0].1.is_some());
+    /// assert!(values[1].1.is_none());
+    /// ```
+    pub fn get_many(&self, tags: &[u32]) -> Vec<(u32, Option<&Item>)> {
+        let items = self.get_data::<Vec<Item>>().unwrap();
+        tags.iter()
+            .map(|tag| (*tag, items.iter().find(|item| item.tag & TAG_MASK == tag & TAG_MASK)))
+            .collect()
+    }
+
+    /// Returns true if the frame contains the `EMS::ALIVE` heartbeat
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// assert!(rscp::ems::alive_frame().is_alive());
+    /// assert!(!rscp::Frame::new().is_alive());
+    /// ```
+    pub fn is_alive(&self) -> bool {
+        self.items().iter().any(|item| item.tag & TAG_MASK == crate::tags::EMS::ALIVE as u32)
+    }
+
+    /// Returns true if the frame contains a request changing the state of the device, see `tags::is_write`
+    ///
+    /// Such frames are not safe to resend after a timeout or connection loss without the consent
+    /// of the caller, as the device may have applied them already.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{tags, Frame};
+    /// assert!(rscp::se::set_ep_reserve(20.0).is_write());
+    /// assert!(!Frame::request_tags(&[tags::EMS::POWER_PV.into()]).is_write());
+    /// ```
+    pub fn is_write(&self) -> bool {
+        self.items().iter().any(|item| crate::tags::is_write(item.tag))
+    }
+
+    /// Returns an owned copy of the typed data of the item with the tag
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - the tag of the item
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{tags, Item, Frame};
+    /// let mut frame = Frame::new();
+    /// frame.push_item(Item::new(tags::EMS::POWER_PV.into(), 1200i32));
+    /// let power_pv: i32 = frame.value(tags::EMS::POWER_PV.into()).unwrap();
+    /// ```
+    pub fn value<T: 'static + Clone>(&self, tag: u32) -> Result<T> {
+        let item = self.get_item(tag)?;
+        match item.data.as_ref().and_then(|data| data.downcast_ref::<T>()) {
+            Some(value) => Ok(value.clone()),
+            None => Err(anyhow!(Errors::TypeMismatch {
+                expected: get_type_id_data_type(TypeId::of::<T>()).unwrap_or(DataType::Error),
+                actual: get_data_type(item.data.as_ref()).unwrap_or(DataType::Error),
+            })),
+        }
+    }
+
+    /// Returns the differences of the items to the other frame, containers are compared recursively
+    ///
+    /// Items are matched by tag, multiple items with the same tag are matched in order.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - the frame to compare with
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{tags, Item, Frame};
+    /// let mut before = Frame::new();
+    /// before.push_item(Item::new(tags::EMS::POWER_PV.into(), 1200i32));
+    /// let mut after = Frame::new();
+    /// after.push_item(Item::new(tags::EMS::POWER_PV.into(), 1500i32));
+    /// for difference in before.diff(&after) {
+    ///     println!("{:?}", difference);
+    /// }
+    /// ```
+    pub fn diff(&self, other: &Frame) -> Vec<Difference> {
+        let mut differences = Vec::new();
+        diff_items(&[], self.items(), other.items(), &mut differences);
+        differences
+    }
+
+    /// Returns (tag, expected, actual) for each item of well known tags with an unexpected data type
+    ///
+    /// This is a diagnostic for firmware sending other types than the protocol defines, items
+    /// without data or with error code are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{tags, Item, Frame};
+    /// let mut frame = Frame::new();
+    /// frame.push_item(Item::new(tags::EMS::POWER_PV.into(), 1200u32));
+    /// for (tag, expected, actual) in frame.validate_types() {
+    ///     println!("{:#010x}: expected {:?} got {:?}", tag, expected, actual);
+    /// }
+    /// ```
+    pub fn validate_types(&self) -> Vec<(u32, DataType, DataType)> {
+        let mut mismatches = Vec::new();
+        validate_types(self.get_data::<Vec<Item>>().unwrap(), &mut mismatches);
+        mismatches
+    }
+
+    /// Serializes the frame once and returns it as frozen frame, which can be sent repeatedly
+    /// without serializing it again. The timestamp of the frame is frozen as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{tags, Item, Frame};
+    /// let mut info_frame = Frame::new();
+    /// info_frame.push_item(Item { tag: tags::INFO::SERIAL_NUMBER.into(), data: None } );
+    /// let frozen_frame = info_frame.freeze().unwrap();
+    /// ```
+    pub fn freeze(self) -> Result<FrozenFrame> {
+        let bytes = self.to_bytes()?;
+        Ok(FrozenFrame { frame: self, bytes })
+    }
+
     /// Returns data frame a byte vector
     ///
     /// # Examples
@@ -83,45 +599,60 @@ impl Frame {
     /// ```
     pub fn to_bytes(&self) -> Result<Vec<u8>> {
         let data_length = get_data_length(&DataType::Container, self.items.as_ref())?;
-        let crc_sum: Crc<u32> = Crc::<u32>::new(&CRC_32_ISO_HDLC);
-
-        let mut buffer: Cursor<Vec<u8>> = Cursor::new(Vec::new());
+        let mut buffer: Cursor<Vec<u8>> = Cursor::new(Vec::with_capacity(FRAME_HEADER_SIZE + data_length as usize + CHECKSUM_SIZE));
 
         // magic ID is big endian
-        buffer.write(&MAGIC_ID.to_be_bytes())?;
+        buffer.write_all(&MAGIC_ID.to_be_bytes())?;
 
         // documentation missmatch of version flag
-        buffer.write(&[0x00u8])?;
+        buffer.write_all(&[0x00u8])?;
 
         // add protocol version and checksum flag
         if self.with_checksum {
-            buffer.write(&[PROTOCOL_VERSION | WITH_CHECKSUM])?;
+            buffer.write_all(&[(self.protocol_version & PROTOCOL_VERSION_MASK) | WITH_CHECKSUM])?;
         } else {
-            buffer.write(&[PROTOCOL_VERSION])?;
+            buffer.write_all(&[self.protocol_version & PROTOCOL_VERSION_MASK])?;
         }
 
         // write timestamp to data
         write_timestamp(&mut buffer, &self.time_stamp)?;
 
         // writes the current
-        buffer.write(&data_length.to_le_bytes())?;
+        buffer.write_all(&data_length.to_le_bytes())?;
 
         // writes the container data
         write_data(&mut buffer, &DataType::Container, self.items.as_ref())?;
 
         if self.with_checksum {
             // calculates CRC sum
-            let sum = crc_sum.checksum(buffer.get_ref());
+            let sum = rscp_crc32(buffer.get_ref());
 
             // write crc sum
-            buffer.write(&sum.to_le_bytes())?;
+            buffer.write_all(&sum.to_le_bytes())?;
         }
 
-        Ok(buffer.get_ref().to_vec())
+        Ok(buffer.into_inner())
+    }
+
+    /// Returns hex dump of the serialized frame, see `hexdump`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{tags, Item, Frame};
+    /// let mut info_frame = Frame::new();
+    /// info_frame.push_item(Item { tag: tags::INFO::SERIAL_NUMBER.into(), data: None } );
+    /// println!("{}", info_frame.hexdump().unwrap());
+    /// ```
+    pub fn hexdump(&self) -> Result<String> {
+        Ok(hexdump(&self.to_bytes()?))
     }
 
     /// Returns data frame from a byte vector
     ///
+    /// Bytes following the frame must be zero padding like added by the encryption, other
+    /// trailing bytes indicate misframing and are an error.
+    ///
     /// # Examples
     ///
     /// ```
@@ -129,43 +660,129 @@ impl Frame {
     /// let frame = Frame::from_bytes(vec![0xe3, 0xdc, 0x00, 0x11, 0x95, 0x23, 0x86, 0x62, 0x00, 0x00, 0x00, 0x00, 0x90, 0x1d, 0x45, 0x35, 0x08, 0x00, 0x01, 0x00, 0x80, 0x00, 0x03, 0x01, 0x00, 0x0a, 0x0f, 0x24, 0x01, 0x23, 0x00, 0x00]);
     /// ```
     pub fn from_bytes(data: Vec<u8>) -> Result<Self> {
-        let mut buffer: Cursor<Vec<u8>> = Cursor::new(data);
-        let crc_sum: Crc<u32> = Crc::<u32>::new(&CRC_32_ISO_HDLC);
+        Self::from_slice(&data)
+    }
 
-        // magic ID is big endian
-        if buffer.read_be::<u16>()? != MAGIC_ID {
-            bail!(Errors::Parse("Invalid magic header".to_string()))
+    /// Returns data frame from the serialized data, see `from_bytes`
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - the frame data
+    pub(crate) fn from_slice(data: &[u8]) -> Result<Self> {
+        let (frame, _) = Self::parse(data, true, false)?;
+        Ok(frame)
+    }
+
+    /// Returns all data frames of a byte vector, e.g. a decrypted buffer containing multiple responses
+    ///
+    /// Zero bytes between and after the frames, as added by the padding of the encryption, are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::Frame;
+    /// let mut data = Frame::new().to_bytes().unwrap();
+    /// data.resize(32, 0);
+    /// let frames = Frame::from_bytes_many(data).unwrap();
+    /// assert_eq!(frames.len(), 1);
+    /// ```
+    pub fn from_bytes_many(data: Vec<u8>) -> Result<Vec<Self>> {
+        let mut frames = Vec::new();
+        let mut position = 0;
+        loop {
+            // frames start with the magic id, so leading zero bytes are padding
+            while position < data.len() && data[position] == 0 {
+                position += 1;
+            }
+            if position == data.len() {
+                break;
+            }
+            let size = frame_size(&data[position..]).unwrap_or(data.len() - position).min(data.len() - position);
+            frames.push(Self::from_bytes(data[position..position + size].to_vec())?);
+            position += size;
         }
+        Ok(frames)
+    }
 
-        // documentation missmatch of version flag
-        buffer.read_le::<u8>()?;
+    /// Returns data frame from a byte vector and whether the checksum matched
+    ///
+    /// In contrast to `from_bytes` a checksum mismatch is not an error, the frame is parsed
+    /// anyway. Frames without checksum are reported as matching. Malformed items are still an
+    /// error, see `from_bytes_lenient_items`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::Frame;
+    /// let (frame, checksum_ok) = Frame::from_bytes_lenient(vec![0xe3, 0xdc, 0x00, 0x11, 0x95, 0x23, 0x86, 0x62, 0x00, 0x00, 0x00, 0x00, 0x90, 0x1d, 0x45, 0x35, 0x08, 0x00, 0x01, 0x00, 0x80, 0x00, 0x03, 0x01, 0x00, 0x0a, 0x0f, 0x24, 0x01, 0x23, 0x00, 0x00]).unwrap();
+    /// if !checksum_ok {
+    ///     println!("Frame is corrupt: {:?}", frame);
+    /// }
+    /// ```
+    pub fn from_bytes_lenient(data: Vec<u8>) -> Result<(Self, bool)> {
+        Self::parse(&data, false, false)
+    }
 
-        // protocol version and checksum flag
-        let prot_ver = buffer.read_le::<u8>()?;
-        if prot_ver & PROTOCOL_VERSION_MASK != PROTOCOL_VERSION {
-            bail!(Errors::Parse(format!("Invalid Protocol version, got {:?}", prot_ver)))
-        }
+    /// Returns data frame from a byte vector and whether the checksum matched, malformed items
+    /// are replaced by placeholders
+    ///
+    /// Like `from_bytes_lenient`, additionally a malformed item does not fail the frame, it is
+    /// replaced by an item with error data and parsing resumes after it, see
+    /// `Item::read_bytes_lenient`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{ErrorCode, Frame, GetItem, Item, tags};
+    /// let mut frame = Frame::new();
+    /// frame.push_item(Item::new(tags::INFO::SERIAL_NUMBER.into(), "serial".to_string()));
+    /// let mut data = frame.to_bytes().unwrap();
+    /// data[25] = 0xff;
+    /// let (frame, _) = Frame::from_bytes_lenient_items(data).unwrap();
+    /// assert_eq!(frame.get_item_data::<ErrorCode>(tags::INFO::SERIAL_NUMBER.into()).unwrap(), &ErrorCode::Format);
+    /// ```
+    pub fn from_bytes_lenient_items(data: Vec<u8>) -> Result<(Self, bool)> {
+        Self::parse(&data, false, true)
+    }
 
-        let with_checksum = if prot_ver & WITH_CHECKSUM == WITH_CHECKSUM { true } else { false };
+    /// Parses the frame, a checksum mismatch or trailing garbage is an error in strict mode
+    fn parse(data: &[u8], strict: bool, lenient_items: bool) -> Result<(Self, bool)> {
+        let mut items: Vec<Item> = Vec::new();
+        let (mut frame, checksum_ok) = Self::parse_with(data, strict, lenient_items, |item| items.push(item))?;
+        frame.items = Some(Box::new(items));
+        Ok((frame, checksum_ok))
+    }
 
-        // read timestamp
-        let time_stamp = read_timestamp(&mut buffer)?;
+    /// Parses the frame and passes each top-level item to the callback instead of collecting
+    /// them, the items of the returned frame are empty
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - the frame data
+    /// * `strict` - a checksum mismatch or trailing garbage is an error
+    /// * `lenient_items` - replace malformed items by placeholders instead of failing
+    /// * `on_item` - callback for each top-level item
+    fn parse_with<F: FnMut(Item)>(data: &[u8], strict: bool, lenient_items: bool, mut on_item: F) -> Result<(Self, bool)> {
+        let mut buffer: Cursor<&[u8]> = Cursor::new(data);
 
-        // read data length
-        let length = buffer.read_le::<u16>()?;
+        let (protocol_version, with_checksum, time_stamp, length) = read_frame_header(&mut buffer)?;
 
+        let mut checksum_ok = true;
         if with_checksum {
             // save current data length
             let data_start = buffer.position();
 
             // length of data for checksum calc
             let data_check_length = length as usize + data_start as usize;
+            if buffer.get_ref().len() < data_check_length + CHECKSUM_SIZE {
+                bail!(Errors::Parse("frame too short".to_string()))
+            }
 
             // set position to start
             buffer.set_position(0);
 
             // calculate checksum
-            let sum = crc_sum.checksum(&buffer.get_ref()[..data_check_length]);
+            let sum = rscp_crc32(&buffer.get_ref()[..data_check_length]);
 
             // move position to checksum
             buffer.set_position(data_check_length as u64);
@@ -173,7 +790,10 @@ impl Frame {
             // read checksum
             let cksum = buffer.read_le::<u32>()?;
             if cksum != sum {
-                bail!(Errors::Parse(format!("CRC Checksum missmatch, got {:?} = {:?}", cksum, sum)))
+                if strict {
+                    bail!(Errors::ChecksumMismatch { got: cksum, expected: sum })
+                }
+                checksum_ok = false;
             }
 
             // set position back to data
@@ -181,17 +801,201 @@ impl Frame {
         }
 
         // parse items
-        let mut items: Vec<Item> = Vec::new();
         let mut container_size = length;
         while container_size > 0 {
-            items.push(Item::read_bytes(&mut buffer, &mut container_size)?);
+            if lenient_items {
+                on_item(Item::read_bytes_lenient(&mut buffer, &mut container_size)?);
+            } else {
+                on_item(Item::read_bytes(&mut buffer, &mut container_size)?);
+            }
         }
 
-        Ok(Self {
+        // only zero padding is allowed after the frame
+        let frame_end = FRAME_HEADER_SIZE + length as usize + if with_checksum { CHECKSUM_SIZE } else { 0 };
+        if strict && buffer.get_ref().iter().skip(frame_end).any(|byte| *byte != 0) {
+            bail!(Errors::Parse(format!("Non-zero trailing bytes after frame of {} bytes", frame_end)))
+        }
+
+        let frame = Self {
             with_checksum: with_checksum,
+            protocol_version,
             time_stamp: time_stamp,
-            items: Some(Box::new(items)),
-        })
+            items: Some(Box::new(Vec::<Item>::new())),
+            data_length: Some(length),
+        };
+        Ok((frame, checksum_ok))
+    }
+}
+
+/// Returns canonical hex dump of the data with offset, hex bytes and ASCII columns like `hexdump -C`
+///
+/// # Arguments
+///
+/// * `data` - the data to dump
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(rscp::hexdump(&[0xe3, 0xdc, 0x00, 0x11]), "00000000  e3 dc 00 11                                       |....|\n00000004\n");
+/// ```
+pub fn hexdump(data: &[u8]) -> String {
+    let mut dump = String::new();
+    for (line_index, line) in data.chunks(16).enumerate() {
+        dump.push_str(&format!("{:08x} ", line_index * 16));
+        for index in 0..16 {
+            if index % 8 == 0 {
+                dump.push(' ');
+            }
+            match line.get(index) {
+                Some(byte) => dump.push_str(&format!("{:02x} ", byte)),
+                None => dump.push_str("   "),
+            }
+        }
+        let ascii: String = line.iter().map(|byte| if byte.is_ascii_graphic() || *byte == b' ' { *byte as char } else { '.' }).collect();
+        dump.push_str(&format!(" |{}|\n", ascii));
+    }
+    dump.push_str(&format!("{:08x}\n", data.len()));
+    dump
+}
+
+/// Returns the CRC-32 (ISO-HDLC) of the data, the same checksum is appended to frames
+///
+/// # Arguments
+///
+/// * `data` - the data to calculate the checksum of
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(rscp::rscp_crc32(b"123456789"), 0xcbf43926);
+/// ```
+pub fn rscp_crc32(data: &[u8]) -> u32 {
+    RSCP_CRC.checksum(data)
+}
+
+/// Logs each item of the frame with tag, type and value on separate lines at trace level
+///
+/// # Arguments
+///
+/// * `frame` - the frame to log
+///
+/// # Examples
+///
+/// ```
+/// use rscp::{tags, trace_frame, Item, Frame};
+/// let mut info_frame = Frame::new();
+/// info_frame.push_item(Item { tag: tags::INFO::SERIAL_NUMBER.into(), data: None } );
+/// trace_frame(&info_frame);
+/// ```
+pub fn trace_frame(frame: &Frame) {
+    trace!(target: crate::LOG_TARGET, "Frame time_stamp: {}, with_checksum: {}", frame.time_stamp, frame.with_checksum);
+    for item in frame.get_data::<Vec<Item>>().unwrap() {
+        trace_item(item, 1);
+    }
+}
+
+/// Reads the frame header, returns the protocol version, the checksum flag, the timestamp and the
+/// data length
+///
+/// # Arguments
+///
+/// * `buffer` - read cursor at the start of the frame
+fn read_frame_header(buffer: &mut Cursor<&[u8]>) -> Result<(u8, bool, DateTime<Utc>, u16)> {
+    // magic and control bytes are required to identify the frame
+    if buffer.get_ref().len() < 4 {
+        bail!(Errors::Parse("frame too short".to_string()))
+    }
+
+    // magic ID is big endian
+    let magic = buffer.read_be::<u16>()?;
+    if magic != MAGIC_ID {
+        bail!(Errors::InvalidMagic { got: magic })
+    }
+
+    // documentation missmatch of version flag
+    buffer.read_le::<u8>()?;
+
+    // protocol version and checksum flag
+    let prot_ver = buffer.read_le::<u8>()?;
+    let protocol_version = prot_ver & PROTOCOL_VERSION_MASK;
+    if !SUPPORTED_PROTOCOL_VERSIONS.contains(&protocol_version) {
+        bail!(Errors::UnsupportedVersion { got: prot_ver })
+    }
+
+    let with_checksum = if prot_ver & WITH_CHECKSUM == WITH_CHECKSUM { true } else { false };
+
+    if buffer.get_ref().len() < FRAME_HEADER_SIZE {
+        bail!(Errors::Parse("frame too short".to_string()))
+    }
+
+    // read timestamp
+    let time_stamp = read_timestamp(buffer)?;
+
+    // read data length
+    let length = buffer.read_le::<u16>()?;
+
+    Ok((protocol_version, with_checksum, time_stamp, length))
+}
+
+/// Returns the size of the serialized frame at the start of data, or None if data is shorter than the header
+/// or does not start with the magic id
+///
+/// # Arguments
+///
+/// * `data` - serialized frame data
+pub(crate) fn frame_size(data: &[u8]) -> Option<usize> {
+    if data.len() < FRAME_HEADER_SIZE || data[..2] != MAGIC_ID.to_be_bytes() {
+        return None;
+    }
+    let length = u16::from_le_bytes([data[FRAME_HEADER_SIZE - 2], data[FRAME_HEADER_SIZE - 1]]) as usize;
+    if data[3] & WITH_CHECKSUM == WITH_CHECKSUM {
+        Some(FRAME_HEADER_SIZE + length + CHECKSUM_SIZE)
+    } else {
+        Some(FRAME_HEADER_SIZE + length)
+    }
+}
+
+/// Collects the differences of two item lists
+///
+/// # Arguments
+///
+/// * `path` - tags of the parent containers
+/// * `old` - the items of this frame
+/// * `new` - the items of the other frame
+/// * `differences` - the list of differences
+fn diff_items(path: &[u32], old: &[Item], new: &[Item], differences: &mut Vec<Difference>) {
+    // returns the number of preceding items with the same tag as the item at the index
+    fn occurrence(items: &[Item], index: usize) -> usize {
+        items[..index].iter().filter(|item| item.tag & TAG_MASK == items[index].tag & TAG_MASK).count()
+    }
+    // returns the nth item with the tag
+    fn find(items: &[Item], tag: u32, occurrence: usize) -> Option<&Item> {
+        items.iter().filter(|item| item.tag & TAG_MASK == tag).nth(occurrence)
+    }
+
+    for (index, old_item) in old.iter().enumerate() {
+        let tag = old_item.tag & TAG_MASK;
+        let mut item_path = path.to_vec();
+        item_path.push(tag);
+        match find(new, tag, occurrence(old, index)) {
+            Some(new_item) => match (old_item.as_container(), new_item.as_container()) {
+                (Ok(old_items), Ok(new_items)) => diff_items(&item_path, old_items, new_items, differences),
+                _ => {
+                    if old_item.to_bytes().ok() != new_item.to_bytes().ok() {
+                        differences.push(Difference::Changed { path: item_path, old: old_item.clone(), new: new_item.clone() });
+                    }
+                }
+            },
+            None => differences.push(Difference::Removed { path: item_path, item: old_item.clone() }),
+        }
+    }
+    for (index, new_item) in new.iter().enumerate() {
+        let tag = new_item.tag & TAG_MASK;
+        if find(old, tag, occurrence(new, index)).is_none() {
+            let mut item_path = path.to_vec();
+            item_path.push(tag);
+            differences.push(Difference::Added { path: item_path, item: new_item.clone() });
+        }
     }
 }
 
@@ -220,14 +1024,31 @@ impl GetItem for Frame {
     }
 }
 
+impl ItemContainer for Frame {
+    fn items(&self) -> &[Item] {
+        self.items.items()
+    }
+}
+
+impl IntoIterator for Frame {
+    type Item = Item;
+    type IntoIter = std::vec::IntoIter<Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_items().into_iter()
+    }
+}
+
 impl Clone for Frame {
     fn clone(&self) -> Self {
         let items_cloned = self.get_data::<Vec<Item>>().unwrap().clone();
         let items_boxed: Box<Vec<Item>> = Box::new(items_cloned.to_vec());
         Self {
             with_checksum: self.with_checksum.clone(),
+            protocol_version: self.protocol_version,
             time_stamp: self.time_stamp.clone(),
             items: Some(items_boxed),
+            data_length: self.data_length,
         }
     }
 }
@@ -243,10 +1064,110 @@ fn test_new() {
     assert_eq!(frame.items.unwrap().downcast_ref::<Vec<Item>>().unwrap().len(), 0);
 }
 
+#[test]
+fn test_new_with_checksum() {
+    let frame = Frame::new_with_checksum(false);
+    assert!(!frame.with_checksum);
+    let data = frame.to_bytes().unwrap();
+    assert_eq!(data.len(), FRAME_HEADER_SIZE);
+    assert_eq!(data[3], PROTOCOL_VERSION);
+
+    let frame = Frame::new_with_checksum(true);
+    assert!(frame.with_checksum);
+    let data = frame.to_bytes().unwrap();
+    assert_eq!(data.len(), FRAME_HEADER_SIZE + CHECKSUM_SIZE);
+    assert_eq!(data[3], PROTOCOL_VERSION | WITH_CHECKSUM);
+}
+
+#[test]
+fn test_request_tags() {
+    let tags: [u32; 3] = [crate::tags::EMS::POWER_PV.into(), crate::tags::EMS::POWER_BAT.into(), crate::tags::EMS::BAT_SOC.into()];
+    let frame = Frame::request_tags(&tags);
+    assert!(frame.with_checksum);
+    assert_eq!(frame.items().len(), 3);
+    assert_eq!(frame.items().iter().map(|item| item.tag).collect::<Vec<u32>>(), tags);
+    assert!(frame.items().iter().all(|item| item.data.is_none()));
+    assert!(Frame::request_tags(&[]).items().is_empty());
+}
+
+#[test]
+fn test_is_write() {
+    let mut frame = Frame::request_tags(&[crate::tags::EMS::POWER_PV.into(), crate::tags::EMS::BAT_SOC.into()]);
+    assert!(!frame.is_write());
+    frame.push_item(Item::new_tag(crate::tags::EMS::SET_POWER, vec![Item::new_tag(crate::tags::EMS::SET_POWER_MODE, 0u8)]));
+    assert!(frame.is_write());
+    assert!(!Frame::new().is_write());
+}
+
+#[test]
+fn test_push_container() {
+    let mut frame = Frame::new();
+    frame.push_container(crate::tags::SE::EP_RESERVE.into(), |c| {
+        c.item(Item::new_tag(crate::tags::SE::PARAM_INDEX, 0u8));
+    });
+    let mut expected = Frame::new();
+    expected.push_item(Item::new_tag(crate::tags::SE::EP_RESERVE, vec![Item::new_tag(crate::tags::SE::PARAM_INDEX, 0u8)]));
+    assert!(frame.diff(&expected).is_empty());
+}
+
+#[test]
+fn test_into_iter() {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(crate::tags::EMS::POWER_PV, 1200i32));
+    frame.push_item(Item::new_tag(crate::tags::INFO::SERIAL_NUMBER, "serial".to_string()));
+
+    let mut items = Vec::new();
+    for item in frame {
+        items.push(item);
+    }
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].tag, crate::tags::EMS::POWER_PV as u32);
+    assert_eq!(items[0].as_i32().unwrap(), 1200);
+    assert_eq!(items.remove(1).data.unwrap().downcast::<String>().unwrap().as_str(), "serial");
+
+    assert!(Frame::new().into_iter().next().is_none());
+}
+
+#[test]
+fn test_set_clock() {
+    let time_stamp = DateTime::<Utc>::from_timestamp(1700000000, 123456789).unwrap();
+    Frame::set_clock(Some(Box::new(move || time_stamp)));
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(crate::tags::EMS::POWER_PV, 1200i32));
+    assert_eq!(frame.time_stamp, time_stamp);
+    let mut other = Frame::new();
+    other.push_item(Item::new_tag(crate::tags::EMS::POWER_PV, 1200i32));
+    assert_eq!(frame.to_bytes().unwrap(), other.to_bytes().unwrap());
+    assert_eq!(frame.to_bytes().unwrap()[4..16], [0x00, 0xf1, 0x53, 0x65, 0x00, 0x00, 0x00, 0x00, 0x15, 0xcd, 0x5b, 0x07]);
+
+    Frame::set_clock(None);
+    assert_ne!(Frame::new().time_stamp, time_stamp);
+}
+
+#[test]
+fn test_append() {
+    let mut frame = Frame::new();
+    frame.push_item(Item::from_tag(crate::tags::INFO::SERIAL_NUMBER));
+    frame.push_item(Item::from_tag(crate::tags::INFO::SW_RELEASE));
+    let mut other = Frame::new();
+    other.push_item(Item::from_tag(crate::tags::EMS::POWER_PV));
+    other.push_item(Item::from_tag(crate::tags::EMS::POWER_BAT));
+
+    frame.append(other);
+    frame.append(Frame::new());
+    let tags: Vec<u32> = frame.get_data::<Vec<Item>>().unwrap().iter().map(|item| item.tag).collect();
+    assert_eq!(tags, vec![
+        crate::tags::INFO::SERIAL_NUMBER as u32,
+        crate::tags::INFO::SW_RELEASE as u32,
+        crate::tags::EMS::POWER_PV as u32,
+        crate::tags::EMS::POWER_BAT as u32,
+    ]);
+}
+
 #[test]
 fn test_push_item() {
     let mut frame = Frame::new();
-    frame.push_item(Item { tag: crate::tags::INFO::SERIAL_NUMBER.into(), data: None });
+    frame.push_item(Item::from_tag(crate::tags::INFO::SERIAL_NUMBER));
     assert_eq!(frame.items.unwrap().downcast_ref::<Vec<Item>>().unwrap().len(), 1);
 }
 
@@ -254,15 +1175,19 @@ fn test_push_item() {
 fn test_to_bytes() {
     let frame = Frame {
         with_checksum: true,
+        protocol_version: PROTOCOL_VERSION,
         time_stamp: DateTime::<Utc>::from_timestamp(12345678, 123456).unwrap(),
-        items: Some(Box::new(vec![Item { tag: crate::tags::INFO::SERIAL_NUMBER.into(), data: None }])),
+        items: Some(Box::new(vec![Item::from_tag(crate::tags::INFO::SERIAL_NUMBER)])),
+        ..Frame::new()
     };
     assert_eq!(frame.to_bytes().unwrap(), vec![0xe3, 0xdc, 0x00, 0x11, 0x4e, 0x61, 0xbc, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0xe2, 0x01, 0x00, 0x07, 0x00, 0x01, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x00, 0xfe, 0xfa, 0x84, 0x3c]);
 
     let frame = Frame {
         with_checksum: false,
+        protocol_version: PROTOCOL_VERSION,
         time_stamp: DateTime::<Utc>::from_timestamp(12345678, 123456).unwrap(),
-        items: Some(Box::new(vec![Item { tag: crate::tags::INFO::SERIAL_NUMBER.into(), data: None }])),
+        items: Some(Box::new(vec![Item::from_tag(crate::tags::INFO::SERIAL_NUMBER)])),
+        ..Frame::new()
     };
     assert_eq!(frame.to_bytes().unwrap(), vec![0xe3, 0xdc, 0x00, 0x01, 0x4e, 0x61, 0xbc, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0xe2, 0x01, 0x00, 0x07, 0x00, 0x01, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x00]);
 }
@@ -271,27 +1196,70 @@ fn test_to_bytes() {
 fn test_from_bytes() {
     let frame = Frame::from_bytes(vec![0xe3, 0xdc, 0x00, 0x11, 0x4e, 0x61, 0xbc, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0xe2, 0x01, 0x00, 0x07, 0x00, 0x01, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x00, 0xfe, 0xfa, 0x84, 0x3c]).unwrap();
     assert_eq!(frame.with_checksum, true);
+    assert_eq!(frame.protocol_version(), 0x01);
     assert_eq!(frame.items.unwrap().downcast_ref::<Vec<Item>>().unwrap().len(), 1);
 
     let frame = Frame::from_bytes(vec![0xe3, 0xdc, 0x00, 0x01, 0x4e, 0x61, 0xbc, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0xe2, 0x01, 0x00, 0x07, 0x00, 0x01, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x00]).unwrap();
     assert_eq!(frame.with_checksum, false);
 
     let frame_err = Frame::from_bytes(vec![0xaa, 0xdc, 0x00, 0x00]);
-    assert_eq!(format!("{}", frame_err.unwrap_err().downcast::<Errors>().unwrap()), "Frame parse error: Invalid magic header");
+    assert!(matches!(frame_err.unwrap_err().downcast::<Errors>().unwrap(), Errors::InvalidMagic { got: 0xaadc }));
 
     let frame_err = Frame::from_bytes(vec![0xe3, 0xdc, 0x00, 0x00]);
-    assert_eq!(format!("{}", frame_err.unwrap_err().downcast::<Errors>().unwrap()), "Frame parse error: Invalid Protocol version, got 0");
+    assert!(matches!(frame_err.unwrap_err().downcast::<Errors>().unwrap(), Errors::UnsupportedVersion { got: 0 }));
+
+    let frame_err = Frame::from_bytes(vec![0xe3, 0xdc, 0x00, 0x13]);
+    assert!(matches!(frame_err.unwrap_err().downcast::<Errors>().unwrap(), Errors::UnsupportedVersion { got: 19 }));
+
+    let frame_err = Frame::from_bytes(b"HTTP/1.1 400 Bad Request".to_vec());
+    assert_eq!(format!("{}", frame_err.unwrap_err()), "Invalid magic header, got 0x4854");
 
     let frame_err = Frame::from_bytes(vec![0xe3, 0xdc, 0x00, 0x11, 0x4e, 0x61, 0xbc, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0xe2, 0x01, 0x00, 0x07, 0x00, 0x01, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x00, 0xfe, 0xfa, 0x84, 0x33]);
-    assert_eq!(format!("{}", frame_err.unwrap_err().downcast::<Errors>().unwrap()), "Frame parse error: CRC Checksum missmatch, got 864353022 = 1015347966");
+    assert!(matches!(frame_err.unwrap_err().downcast::<Errors>().unwrap(), Errors::ChecksumMismatch { got: 864353022, expected: 1015347966 }));
+}
+
+#[test]
+fn test_rscp_crc32() {
+    assert_eq!(rscp_crc32(b"123456789"), 0xcbf43926);
+    assert_eq!(rscp_crc32(&[]), 0x00000000);
+
+    let mut frame = Frame::new().with_checksum();
+    frame.push_item(Item::new_tag(crate::tags::INFO::SERIAL_NUMBER, "serial".to_string()));
+    let data = frame.to_bytes().unwrap();
+    let (data, checksum) = data.split_at(data.len() - CHECKSUM_SIZE);
+    assert_eq!(rscp_crc32(data).to_le_bytes(), checksum);
+}
+
+#[test]
+fn test_hexdump() {
+    let frame = Frame {
+        with_checksum: true,
+        protocol_version: PROTOCOL_VERSION,
+        time_stamp: DateTime::<Utc>::from_timestamp(12345678, 123456).unwrap(),
+        items: Some(Box::new(vec![Item::from_tag(crate::tags::INFO::SERIAL_NUMBER)])),
+        ..Frame::new()
+    };
+    let dump = frame.hexdump().unwrap();
+    assert!(dump.starts_with("00000000  e3 dc 00 11 "));
+    assert_eq!(dump.lines().count(), 3);
+    assert!(dump.ends_with("\n0000001d\n"));
+
+    assert_eq!(hexdump(b"0123456789abcdefRSCP\x00\xff"), concat!(
+        "00000000  30 31 32 33 34 35 36 37  38 39 61 62 63 64 65 66  |0123456789abcdef|\n",
+        "00000010  52 53 43 50 00 ff                                 |RSCP..|\n",
+        "00000016\n"
+    ));
+    assert_eq!(hexdump(&[]), "00000000\n");
 }
 
 #[test]
 fn test_debug_impl() {
     let frame = Frame {
         with_checksum: true,
+        protocol_version: PROTOCOL_VERSION,
         time_stamp: DateTime::<Utc>::from_timestamp(12345678, 123456).unwrap(),
-        items: Some(Box::new(vec![Item { tag: crate::tags::INFO::SERIAL_NUMBER.into(), data: None }])),
+        items: Some(Box::new(vec![Item::from_tag(crate::tags::INFO::SERIAL_NUMBER)])),
+        ..Frame::new()
     };
     assert_eq!(format!("{:?}", frame), "Frame { time_stamp: 1970-05-23T21:21:18.000123456Z, items: [Item { tag: \"INFO_SERIAL_NUMBER\", data: \"None\" }] }");
 }
@@ -300,8 +1268,10 @@ fn test_debug_impl() {
 fn test_clone_item_impl() {
     let frame = Frame {
         with_checksum: true,
+        protocol_version: PROTOCOL_VERSION,
         time_stamp: DateTime::<Utc>::from_timestamp(12345678, 123456).unwrap(),
-        items: Some(Box::new(vec![Item::new(crate::tags::INFO::SERIAL_NUMBER.into(), "serial".to_string())])),
+        items: Some(Box::new(vec![Item::new_tag(crate::tags::INFO::SERIAL_NUMBER, "serial".to_string())])),
+        ..Frame::new()
     };
     let clone_frame = frame.clone();
     println!("{:?}", frame);
@@ -316,11 +1286,339 @@ fn test_clone_item_impl() {
 fn test_get_item_impl() {
     let frame = Frame {
         with_checksum: true,
+        protocol_version: PROTOCOL_VERSION,
         time_stamp: DateTime::<Utc>::from_timestamp(12345678, 123456).unwrap(),
-        items: Some(Box::new(vec![Item::new(crate::tags::INFO::SERIAL_NUMBER.into(), "serial".to_string())])),
+        items: Some(Box::new(vec![Item::new_tag(crate::tags::INFO::SERIAL_NUMBER, "serial".to_string())])),
+        ..Frame::new()
     };
 
     let item = frame.get_item(crate::tags::INFO::SERIAL_NUMBER.into()).unwrap();
     assert_eq!(item.get_data::<String>().unwrap(), "serial");
     assert_eq!(frame.get_item_data::<String>(crate::tags::INFO::SERIAL_NUMBER.into()).unwrap(), "serial");
 }
+
+#[test]
+fn test_response_for() {
+    let mut request = Frame::new();
+    request.push_item(Item::from_tag(crate::tags::INFO::SERIAL_NUMBER));
+    request.push_item(Item::from_tag(crate::tags::INFO::MAC_ADDRESS));
+    request.push_item(Item::from_tag(crate::tags::INFO::SW_RELEASE));
+
+    let mut response = Frame::new();
+    response.push_item(Item::new_tag(crate::tags::INFO::SERIAL_NUMBER, "serial".to_string()));
+    response.push_item(Item::new_tag(crate::tags::INFO::MAC_ADDRESS, ErrorCode::AccessDenied));
+
+    let results = response.response_for(&request);
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].0, crate::tags::INFO::SERIAL_NUMBER as u32);
+    assert_eq!(results[0].1.unwrap().get_data::<String>().unwrap(), "serial");
+    assert_eq!(results[1].0, crate::tags::INFO::MAC_ADDRESS as u32);
+    assert_eq!(results[1].1.unwrap_err(), ErrorCode::AccessDenied);
+    assert_eq!(results[2].0, crate::tags::INFO::SW_RELEASE as u32);
+    assert_eq!(results[2].1.unwrap_err(), ErrorCode::NotHandled);
+}
+
+#[test]
+fn test_is_alive() {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(crate::tags::EMS::POWER_PV, 1200i32));
+    assert!(!frame.is_alive());
+    frame.push_item(Item::new(crate::tags::EMS::ALIVE as u32 | 0x00800000, true));
+    assert!(frame.is_alive());
+}
+
+#[test]
+fn test_get_many() {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(crate::tags::EMS::POWER_PV, 1200i32));
+    frame.push_item(Item::new_tag(crate::tags::EMS::POWER_BAT, -300i32));
+    frame.push_item(Item::new_tag(crate::tags::EMS::BAT_SOC, 80u8));
+
+    let tags = [
+        crate::tags::EMS::POWER_PV.into(),
+        crate::tags::EMS::POWER_BAT.into(),
+        crate::tags::EMS::POWER_HOME.into(),
+        crate::tags::EMS::BAT_SOC.into(),
+    ];
+    let values = frame.get_many(&tags);
+    assert_eq!(values.len(), 4);
+    assert_eq!(values[0].0, crate::tags::EMS::POWER_PV as u32);
+    assert_eq!(values[0].1.unwrap().get_data::<i32>().unwrap(), &1200);
+    assert_eq!(values[1].1.unwrap().get_data::<i32>().unwrap(), &-300);
+    assert_eq!(values[2].0, crate::tags::EMS::POWER_HOME as u32);
+    assert!(values[2].1.is_none());
+    assert_eq!(values[3].1.unwrap().get_data::<u8>().unwrap(), &80);
+}
+
+#[test]
+fn test_freeze() {
+    let mut frame = Frame::new().with_checksum();
+    frame.push_item(Item::from_tag(crate::tags::INFO::SERIAL_NUMBER));
+    let bytes = frame.to_bytes().unwrap();
+
+    let frozen_frame = frame.freeze().unwrap();
+    assert_eq!(frozen_frame.as_bytes(), bytes.as_slice());
+    assert_eq!(frozen_frame.frame().to_bytes().unwrap(), bytes);
+    assert_eq!(frozen_frame.into_frame().to_bytes().unwrap(), bytes);
+}
+
+#[test]
+fn test_item_to_bytes() {
+    let item = Item::new_tag(crate::tags::INFO::SERIAL_NUMBER, "serial".to_string());
+    let mut frame = Frame::new().without_checksum();
+    frame.push_item(item.clone());
+    let frame_bytes = frame.to_bytes().unwrap();
+    assert_eq!(item.to_bytes().unwrap(), frame_bytes[FRAME_HEADER_SIZE..]);
+}
+
+#[test]
+fn test_validate_types() {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(crate::tags::EMS::POWER_PV, 1200i32));
+    frame.push_item(Item::new_tag(crate::tags::EMS::POWER_BAT, 300u32));
+    frame.push_item(Item::new_tag(crate::tags::EMS::BAT_SOC, ErrorCode::NotAvailable));
+    frame.push_item(Item::from_tag(crate::tags::EMS::AUTARKY));
+    frame.push_item(Item::new_tag(crate::tags::RSCP::AUTHENTICATION, vec![
+        Item::new_tag(crate::tags::RSCP::AUTHENTICATION_USER, "user".to_string()),
+        Item::new_tag(crate::tags::RSCP::AUTHENTICATION_PASSWORD, 1234u16),
+    ]));
+    assert_eq!(frame.validate_types(), vec![
+        (crate::tags::EMS::POWER_BAT as u32, DataType::Int32, DataType::UInt32),
+        (crate::tags::RSCP::AUTHENTICATION_PASSWORD as u32, DataType::String, DataType::UInt16),
+    ]);
+}
+
+#[test]
+fn test_value() {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(crate::tags::INFO::SERIAL_NUMBER, "serial".to_string()));
+    frame.push_item(Item::new_tag(crate::tags::INFO::UTC_TIME, 1234u32));
+
+    let serial: String = frame.value(crate::tags::INFO::SERIAL_NUMBER.into()).unwrap();
+    assert_eq!(serial, "serial");
+    assert_eq!(frame.value::<u32>(crate::tags::INFO::UTC_TIME.into()).unwrap(), 1234);
+
+    let err = frame.value::<i32>(crate::tags::INFO::UTC_TIME.into()).unwrap_err();
+    assert_eq!(format!("{}", err), "Type mismatch, expected Int32 got UInt32");
+    assert!(frame.value::<u32>(crate::tags::INFO::SW_RELEASE.into()).is_err());
+}
+
+#[test]
+fn test_diff() {
+    let mut before = Frame::new();
+    before.push_item(Item::new_tag(crate::tags::BAT::DATA, vec![
+        Item::new_tag(crate::tags::BAT::INDEX, 0u16),
+        Item::new_tag(crate::tags::BAT::RSOC, 80.0f32),
+    ]));
+    before.push_item(Item::new_tag(crate::tags::EMS::POWER_PV, 1200i32));
+    let mut after = before.clone();
+    assert!(before.diff(&after).is_empty());
+
+    after.items = Some(Box::new(vec![
+        Item::new_tag(crate::tags::BAT::DATA, vec![
+            Item::new_tag(crate::tags::BAT::INDEX, 0u16),
+            Item::new_tag(crate::tags::BAT::RSOC, 81.5f32),
+        ]),
+        Item::new_tag(crate::tags::EMS::POWER_HOME, 500i32),
+    ]));
+    let differences = before.diff(&after);
+    assert_eq!(differences.len(), 3);
+    match &differences[0] {
+        Difference::Changed { path, old, new } => {
+            assert_eq!(path, &vec![crate::tags::BAT::DATA as u32, crate::tags::BAT::RSOC as u32]);
+            assert_eq!(old.as_f32().unwrap(), 80.0);
+            assert_eq!(new.as_f32().unwrap(), 81.5);
+        }
+        difference => panic!("unexpected {:?}", difference),
+    }
+    assert!(matches!(&differences[1], Difference::Removed { path, .. } if path == &vec![crate::tags::EMS::POWER_PV as u32]));
+    assert!(matches!(&differences[2], Difference::Added { path, .. } if path == &vec![crate::tags::EMS::POWER_HOME as u32]));
+}
+
+#[test]
+fn test_trace_frame() {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(crate::tags::BAT::DATA, vec![
+        Item::new_tag(crate::tags::BAT::INDEX, 0u16),
+        Item::new_tag(crate::tags::BAT::DCB_INFO, vec![Item::new_tag(crate::tags::BAT::DCB_INDEX, 0u16), Item::from_tag(crate::tags::BAT::DCB_CYCLE_COUNT)]),
+        Item::new_tag(crate::tags::BAT::RSOC, ErrorCode::NotAvailable),
+    ]));
+    log::set_max_level(log::LevelFilter::Trace);
+    trace_frame(&frame);
+}
+
+#[test]
+fn test_from_bytes_lenient() {
+    let (frame, checksum_ok) = Frame::from_bytes_lenient(vec![0xe3, 0xdc, 0x00, 0x11, 0x4e, 0x61, 0xbc, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0xe2, 0x01, 0x00, 0x07, 0x00, 0x01, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x00, 0xfe, 0xfa, 0x84, 0x3c]).unwrap();
+    assert!(checksum_ok);
+    assert_eq!(frame.items.unwrap().downcast_ref::<Vec<Item>>().unwrap().len(), 1);
+
+    let (frame, checksum_ok) = Frame::from_bytes_lenient(vec![0xe3, 0xdc, 0x00, 0x11, 0x4e, 0x61, 0xbc, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0xe2, 0x01, 0x00, 0x07, 0x00, 0x01, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x00, 0xfe, 0xfa, 0x84, 0x33]).unwrap();
+    assert!(!checksum_ok);
+    assert!(frame.with_checksum);
+    assert!(frame.get_item(crate::tags::INFO::SERIAL_NUMBER.into()).unwrap().data.is_none());
+
+    let (_, checksum_ok) = Frame::from_bytes_lenient(vec![0xe3, 0xdc, 0x00, 0x01, 0x4e, 0x61, 0xbc, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0xe2, 0x01, 0x00, 0x07, 0x00, 0x01, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x00]).unwrap();
+    assert!(checksum_ok);
+
+    // string item with invalid UTF-8 data is replaced
+    let mut frame = Frame::new().without_checksum();
+    frame.push_item(Item::new_tag(crate::tags::INFO::SERIAL_NUMBER, "serial".to_string()));
+    frame.push_item(Item::new_tag(crate::tags::INFO::SW_RELEASE, "release".to_string()));
+    let mut data = frame.to_bytes().unwrap();
+    data[FRAME_HEADER_SIZE + 7] = 0xff;
+    assert!(Frame::from_bytes(data.clone()).is_err());
+    assert!(Frame::from_bytes_lenient(data.clone()).is_err());
+    let (frame, _) = Frame::from_bytes_lenient_items(data).unwrap();
+    assert_eq!(frame.get_item_data::<ErrorCode>(crate::tags::INFO::SERIAL_NUMBER.into()).unwrap(), &ErrorCode::Format);
+    assert_eq!(frame.get_item_data::<String>(crate::tags::INFO::SW_RELEASE.into()).unwrap(), "release");
+
+    let frame_err = Frame::from_bytes_lenient(vec![0xaa, 0xdc, 0x00, 0x00]);
+    assert!(matches!(frame_err.unwrap_err().downcast::<Errors>().unwrap(), Errors::InvalidMagic { got: 0xaadc }));
+}
+
+#[test]
+fn test_from_bytes_many() {
+    let mut first = Frame::new();
+    first.push_item(Item::new_tag(crate::tags::INFO::SERIAL_NUMBER, "serial".to_string()));
+    let mut second = Frame::new().with_checksum();
+    second.push_item(Item::new_tag(crate::tags::INFO::SW_RELEASE, "release".to_string()));
+
+    let mut data = first.to_bytes().unwrap();
+    data.resize(data.len().div_ceil(32) * 32, 0);
+    data.extend(second.to_bytes().unwrap());
+    data.resize(data.len().div_ceil(32) * 32, 0);
+
+    let frames = Frame::from_bytes_many(data.clone()).unwrap();
+    assert_eq!(frames.len(), 2);
+    assert_eq!(frames[0].get_item_data::<String>(crate::tags::INFO::SERIAL_NUMBER.into()).unwrap(), "serial");
+    assert_eq!(frames[1].get_item_data::<String>(crate::tags::INFO::SW_RELEASE.into()).unwrap(), "release");
+
+    let frames = Frame::from_bytes_many(vec![0; 32]).unwrap();
+    assert!(frames.is_empty());
+
+    data.push(0xe3);
+    assert!(Frame::from_bytes_many(data).is_err());
+}
+
+#[test]
+fn test_from_bytes_truncated() {
+    let frame = Frame {
+        with_checksum: false,
+        protocol_version: PROTOCOL_VERSION,
+        time_stamp: DateTime::<Utc>::from_timestamp(12345678, 123456).unwrap(),
+        items: Some(Box::new(vec![Item::new_tag(crate::tags::INFO::SERIAL_NUMBER, "serial".to_string())])),
+        ..Frame::new()
+    };
+    let mut data = frame.to_bytes().unwrap();
+    data.truncate(data.len() - 3);
+    let frame_err = Frame::from_bytes(data);
+    assert_eq!(format!("{}", frame_err.unwrap_err().downcast::<Errors>().unwrap()), "Frame parse error: unexpected end of frame while reading String");
+}
+
+#[test]
+fn test_from_bytes_too_short() {
+    for data in [vec![], vec![0xe3], vec![0xe3, 0xdc, 0x00], vec![0xe3, 0xdc, 0x00, 0x11, 0x4e, 0x61]] {
+        let frame_err = Frame::from_bytes(data);
+        assert_eq!(format!("{}", frame_err.unwrap_err().downcast::<Errors>().unwrap()), "Frame parse error: frame too short");
+    }
+
+    // checksum frame without checksum
+    let frame_err = Frame::from_bytes(vec![0xe3, 0xdc, 0x00, 0x11, 0x4e, 0x61, 0xbc, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0xe2, 0x01, 0x00, 0x07, 0x00, 0x01, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x00]);
+    assert_eq!(format!("{}", frame_err.unwrap_err().downcast::<Errors>().unwrap()), "Frame parse error: frame too short");
+}
+
+#[test]
+fn test_protocol_version() {
+    let mut frame = Frame::new();
+    frame.set_protocol_version(0x32);
+    assert_eq!(frame.protocol_version(), 0x02);
+    let data = frame.to_bytes().unwrap();
+    assert_eq!(data[3], 0x12);
+    assert_eq!(frame.clone().protocol_version(), 0x02);
+
+    let frame = Frame::from_bytes(data).unwrap();
+    assert_eq!(frame.protocol_version(), 0x02);
+}
+
+#[test]
+fn test_data_length() {
+    let frame = Frame::from_bytes(vec![0xe3, 0xdc, 0x00, 0x11, 0x4e, 0x61, 0xbc, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0xe2, 0x01, 0x00, 0x07, 0x00, 0x01, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x00, 0xfe, 0xfa, 0x84, 0x3c]).unwrap();
+    assert_eq!(frame.data_length(), Some(0x0007));
+    assert_eq!(frame.clone().data_length(), Some(0x0007));
+    assert_eq!(frame.to_bytes().unwrap()[16..18], 7u16.to_le_bytes());
+
+    assert_eq!(Frame::new().data_length(), None);
+}
+
+#[test]
+fn test_from_bytes_trailing_bytes() {
+    let data = vec![0xe3, 0xdc, 0x00, 0x11, 0x4e, 0x61, 0xbc, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0xe2, 0x01, 0x00, 0x07, 0x00, 0x01, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x00, 0xfe, 0xfa, 0x84, 0x3c];
+
+    let mut padded = data.clone();
+    padded.resize(32, 0x00);
+    assert!(Frame::from_bytes(padded).is_ok());
+
+    let mut garbage = data.clone();
+    garbage.extend([0x00, 0x01, 0x00]);
+    let frame_err = Frame::from_bytes(garbage.clone());
+    assert_eq!(format!("{}", frame_err.unwrap_err().downcast::<Errors>().unwrap()), "Frame parse error: Non-zero trailing bytes after frame of 29 bytes");
+
+    // lenient parsing ignores trailing bytes
+    assert!(Frame::from_bytes_lenient(garbage).is_ok());
+}
+
+#[test]
+fn test_toggle_checksum() {
+    let data = vec![0xe3, 0xdc, 0x00, 0x11, 0x4e, 0x61, 0xbc, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0xe2, 0x01, 0x00, 0x07, 0x00, 0x01, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x00, 0xfe, 0xfa, 0x84, 0x3c];
+    let frame = Frame::from_bytes(data.clone()).unwrap().without_checksum();
+    assert!(!frame.with_checksum);
+    let stripped = frame.to_bytes().unwrap();
+    assert_eq!(stripped, vec![0xe3, 0xdc, 0x00, 0x01, 0x4e, 0x61, 0xbc, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0xe2, 0x01, 0x00, 0x07, 0x00, 0x01, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x00]);
+
+    let frame = Frame::from_bytes(stripped).unwrap().with_checksum();
+    assert!(frame.with_checksum);
+    assert_eq!(frame.to_bytes().unwrap(), data);
+}
+
+#[test]
+fn test_frame_size() {
+    let data = vec![0xe3, 0xdc, 0x00, 0x11, 0x4e, 0x61, 0xbc, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0xe2, 0x01, 0x00, 0x07, 0x00, 0x01, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x00, 0xfe, 0xfa, 0x84, 0x3c, 0x00, 0x00, 0x00];
+    assert_eq!(frame_size(&data), Some(29));
+    let data = vec![0xe3, 0xdc, 0x00, 0x01, 0x4e, 0x61, 0xbc, 0x00, 0x00, 0x00, 0x00, 0x00, 0x40, 0xe2, 0x01, 0x00, 0x07, 0x00, 0x01, 0x00, 0x00, 0x0a, 0x00, 0x00, 0x00];
+    assert_eq!(frame_size(&data), Some(25));
+    assert_eq!(frame_size(&data[..17]), None);
+    assert_eq!(frame_size(&[0x55; 32]), None);
+}
+
+#[test]
+fn test_frame_reader() {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(crate::tags::INFO::SERIAL_NUMBER, "serial".to_string()));
+    frame.push_item(Item::new_tag(crate::tags::INFO::SW_RELEASE, "release".to_string()));
+    let frame_data = frame.to_bytes().unwrap();
+    let mut data = frame_data.clone();
+    data.resize(64, 0);
+
+    // items are passed on as soon as they are complete
+    let mut reader = FrameReader::new();
+    let mut tags = Vec::new();
+    assert!(!reader.push(&data[..20], |item| tags.push(item.tag)).unwrap());
+    assert!(tags.is_empty());
+    assert!(!reader.push(&data[20..40], |item| tags.push(item.tag)).unwrap());
+    assert_eq!(tags, vec![crate::tags::INFO::SERIAL_NUMBER as u32]);
+    assert!(reader.push(&data[40..], |item| tags.push(item.tag)).unwrap());
+    assert_eq!(tags, vec![crate::tags::INFO::SERIAL_NUMBER as u32, crate::tags::INFO::SW_RELEASE as u32]);
+
+    let mut reader = FrameReader::new();
+    assert!(reader.push(&frame.clone().without_checksum().to_bytes().unwrap(), |_| {}).unwrap());
+
+    data[frame_data.len() - 1] ^= 0xff;
+    let mut reader = FrameReader::new();
+    let err = reader.push(&data, |_| {}).unwrap_err();
+    assert!(matches!(err.downcast_ref::<Errors>(), Some(Errors::ChecksumMismatch { .. })));
+
+    let mut reader = FrameReader::new();
+    let err = reader.push(&[0x55; 32], |_| {}).unwrap_err();
+    assert!(matches!(err.downcast_ref::<Errors>(), Some(Errors::InvalidMagic { got: 0x5555 })));
+}
@@ -1,10 +1,12 @@
-use crate::Item;
+use crate::item::{get_data_type, get_type_id_data_type};
+use crate::tags::as_request;
+use crate::{DataType, Errors, Item};
 use anyhow::{anyhow, Result};
-use std::any::Any;
+use std::any::{Any, TypeId};
 
 /// Item and data getter for Frame and Item
 pub trait GetItem {
-    /// returns typed data from data property
+    /// returns typed data from data property, fails if the item has no data or data of another type
     ///
     /// # Examples
     /// ```
@@ -14,7 +16,7 @@ pub trait GetItem {
     /// ```
     fn get_data<T: 'static + Sized>(&self) -> Result<&T>;
 
-    /// returns item by tag from data / item list
+    /// returns item by tag from data / item list, tags are compared without the response bit
     ///
     /// # Arguments
     ///
@@ -49,16 +51,45 @@ pub trait GetItem {
     fn get_item_data<T: 'static + Sized>(&self, tag: u32) -> Result<&T>;
 }
 
+/// Access to the items of a Frame or container Item, allows generic code over both
+pub trait ItemContainer {
+    /// returns the contained items, empty if the data is not a container
+    ///
+    /// # Examples
+    /// ```
+    /// use rscp::{tags, Frame, Item, ItemContainer};
+    /// fn count(container: &impl ItemContainer) -> usize {
+    ///     container.items().len()
+    /// }
+    /// let mut frame = Frame::new();
+    /// frame.push_item(Item::new(tags::INFO::SERIAL_NUMBER.into(), "serial".to_string()));
+    /// assert_eq!(count(&frame), 1);
+    /// ```
+    fn items(&self) -> &[Item];
+}
+
+/// implementation for data object
+impl ItemContainer for Option<Box<dyn Any>> {
+    fn items(&self) -> &[Item] {
+        self.as_ref().and_then(|data| data.downcast_ref::<Vec<Item>>()).map_or(&[], |items| items.as_slice())
+    }
+}
+
 /// implementation for data object
 impl GetItem for Option<Box<dyn Any>> {
     fn get_data<T: 'static + Sized>(&self) -> Result<&T> {
-        Ok(self.as_ref().unwrap().as_ref().downcast_ref::<T>().unwrap())
+        let data = self.as_ref().ok_or_else(|| anyhow!(Errors::NoData))?;
+        data.downcast_ref::<T>().ok_or_else(|| {
+            anyhow!(Errors::TypeMismatch {
+                expected: get_type_id_data_type(TypeId::of::<T>()).unwrap_or(DataType::Error),
+                actual: get_data_type(Some(data)).unwrap_or(DataType::Error),
+            })
+        })
     }
 
     fn get_item(&self, tag: u32) -> Result<&Item> {
-        let items = self.as_ref().unwrap().downcast_ref::<Vec<Item>>().unwrap();
-        for item in items {
-            if item.tag == tag {
+        for item in self.get_data::<Vec<Item>>()? {
+            if as_request(item.tag) == as_request(tag) {
                 return Ok(item);
             }
         }
@@ -66,7 +97,51 @@ impl GetItem for Option<Box<dyn Any>> {
     }
 
     fn get_item_data<T: 'static + Sized>(&self, tag: u32) -> Result<&T> {
-        let item = self.get_item(tag)?;
-        Ok(item.data.as_ref().unwrap().as_ref().downcast_ref::<T>().unwrap())
+        self.get_item(tag)?.data.get_data()
     }
 }
+
+/// ################################################
+///      TEST TEST TEST
+/// ################################################
+
+#[test]
+fn test_item_container() {
+    use crate::{tags, Frame};
+
+    let items = vec![
+        Item::new_tag(tags::RSCP::AUTHENTICATION_USER, "username".to_string()),
+        Item::new_tag(tags::RSCP::AUTHENTICATION_PASSWORD, "password".to_string()),
+    ];
+    let expected: Vec<u32> = vec![tags::RSCP::AUTHENTICATION_USER.into(), tags::RSCP::AUTHENTICATION_PASSWORD.into()];
+
+    let mut frame = Frame::new();
+    for item in items.clone() {
+        frame.push_item(item);
+    }
+    assert_eq!(tags_of(&frame), expected);
+    assert_eq!(tags_of(&Item::new_tag(tags::RSCP::AUTHENTICATION, items)), expected);
+    assert!(tags_of(&Item::new_tag(tags::RSCP::AUTHENTICATION_USER, "username".to_string())).is_empty());
+    assert!(tags_of(&Item::from_tag(tags::RSCP::AUTHENTICATION)).is_empty());
+}
+
+#[cfg(test)]
+fn tags_of(container: &impl ItemContainer) -> Vec<u32> {
+    container.items().iter().map(|item| item.tag).collect()
+}
+
+#[test]
+fn test_get_data_errors() {
+    use crate::tags;
+
+    let item = Item::from_tag(tags::EMS::POWER_PV);
+    assert!(matches!(item.get_data::<i32>().unwrap_err().downcast::<Errors>().unwrap(), Errors::NoData));
+    assert!(matches!(item.get_item(tags::EMS::POWER_PV.into()).unwrap_err().downcast::<Errors>().unwrap(), Errors::NoData));
+
+    let item = Item::new_tag(tags::EMS::POWER_PV, 1200i32);
+    assert_eq!(item.get_data::<u8>().unwrap_err().to_string(), "Type mismatch, expected UChar8 got Int32");
+    assert_eq!(item.get_item(tags::EMS::POWER_PV.into()).unwrap_err().to_string(), "Type mismatch, expected Container got Int32");
+
+    let container = Item::new_tag(tags::RSCP::AUTHENTICATION, vec![Item::from_tag(tags::RSCP::AUTHENTICATION_USER)]);
+    assert!(matches!(container.get_item_data::<String>(tags::RSCP::AUTHENTICATION_USER.into()).unwrap_err().downcast::<Errors>().unwrap(), Errors::NoData));
+}
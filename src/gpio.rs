@@ -0,0 +1,110 @@
+use anyhow::Result;
+
+use crate::{tags, Frame, GetItem, Item};
+
+/// General purpose IO pin of the device
+#[derive(Clone, PartialEq, Debug)]
+pub struct GpioPin {
+    /// number of the pin
+    pub number: u8,
+
+    /// name of the pin
+    pub name: String,
+
+    /// current value of the pin
+    pub value: bool,
+}
+
+/// Returns frame requesting the list of pins
+pub fn list() -> Frame {
+    let mut frame = Frame::new();
+    frame.push_item(Item::from_tag(tags::GPIO::RSP_LIST));
+    frame
+}
+
+/// Returns frame to set the value of a pin
+///
+/// # Arguments
+///
+/// * `number` - number of the pin
+/// * `value` - the new value
+///
+/// # Examples
+///
+/// ```
+/// let frame = rscp::gpio::set(2, true);
+/// ```
+pub fn set(number: u8, value: bool) -> Frame {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::GPIO::RSP_SET, vec![Item::new_tag(tags::GPIO::TUPEL, vec![
+        Item::new_tag(tags::GPIO::NUMBER, number),
+        Item::new_tag(tags::GPIO::VALUE, value),
+    ])]));
+    frame
+}
+
+/// Returns the pins of the `RSP_LIST` container in the frame
+///
+/// # Arguments
+///
+/// * `frame` - the response frame
+///
+/// # Examples
+///
+/// ```no_run
+/// use rscp::gpio;
+/// let mut c = rscp::Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+/// c.connect("energy.storage.local", None).unwrap();
+/// let result_frame = c.send_receive_frame(&gpio::list()).unwrap();
+/// for pin in gpio::parse_list(&result_frame).unwrap() {
+///     println!("{} {}: {}", pin.number, pin.name, pin.value);
+/// }
+/// ```
+pub fn parse_list(frame: &Frame) -> Result<Vec<GpioPin>> {
+    let mut pins = Vec::new();
+    for tupel in frame.get_item(tags::GPIO::RSP_LIST.into())?.as_container()? {
+        if tupel.tag == tags::GPIO::TUPEL as u32 {
+            pins.push(GpioPin {
+                number: tupel.get_item(tags::GPIO::NUMBER.into())?.as_u8()?,
+                name: tupel.get_item(tags::GPIO::NAME.into())?.as_string()?.to_string(),
+                value: tupel.get_item(tags::GPIO::VALUE.into())?.as_bool()?,
+            });
+        }
+    }
+    Ok(pins)
+}
+
+/// ################################################
+///      TEST TEST TEST
+/// ################################################
+
+#[test]
+fn test_set() {
+    let frame = set(2, true);
+    let tupel = frame.get_item(tags::GPIO::RSP_SET.into()).unwrap().get_item(tags::GPIO::TUPEL.into()).unwrap();
+    assert_eq!(tupel.get_item_data::<u8>(tags::GPIO::NUMBER.into()).unwrap(), &2);
+    assert_eq!(tupel.get_item_data::<bool>(tags::GPIO::VALUE.into()).unwrap(), &true);
+}
+
+#[test]
+fn test_parse_list() {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::GPIO::RSP_LIST, vec![
+        Item::new_tag(tags::GPIO::TUPEL, vec![
+            Item::new_tag(tags::GPIO::NUMBER, 1u8),
+            Item::new_tag(tags::GPIO::NAME, "RELAY_1".to_string()),
+            Item::new_tag(tags::GPIO::VALUE, false),
+        ]),
+        Item::new_tag(tags::GPIO::TUPEL, vec![
+            Item::new_tag(tags::GPIO::NUMBER, 2u8),
+            Item::new_tag(tags::GPIO::NAME, "RELAY_2".to_string()),
+            Item::new_tag(tags::GPIO::VALUE, true),
+        ]),
+    ]));
+
+    assert_eq!(parse_list(&frame).unwrap(), vec![
+        GpioPin { number: 1, name: "RELAY_1".to_string(), value: false },
+        GpioPin { number: 2, name: "RELAY_2".to_string(), value: true },
+    ]);
+    assert!(parse_list(&list()).is_err());
+}
@@ -0,0 +1,286 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use std::net::Ipv4Addr;
+
+use crate::{tags, Frame, GetItem, Item};
+
+/// Identity of the device
+#[derive(Clone, PartialEq, Debug)]
+pub struct DeviceInfo {
+    /// serial number of the device
+    pub serial_number: String,
+
+    /// MAC address of the device
+    pub mac_address: String,
+
+    /// software release of the device
+    pub sw_release: String,
+
+    /// production date of the device
+    pub production_date: String,
+
+    /// numeric platform type of the device
+    pub platform_type: i32,
+}
+
+impl DeviceInfo {
+    /// Returns frame requesting the identity of the device
+    pub fn request() -> Frame {
+        Frame::request_tags(&[
+            tags::INFO::SERIAL_NUMBER.into(),
+            tags::INFO::MAC_ADDRESS.into(),
+            tags::INFO::SW_RELEASE.into(),
+            tags::INFO::PRODUCTION_DATE.into(),
+            tags::INFO::PLATFORM_TYPE.into(),
+        ])
+    }
+
+    /// Returns identity parsed from the response frame
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - the response frame
+    pub fn from_frame(frame: &Frame) -> Result<Self> {
+        Ok(Self {
+            serial_number: frame.get_item(tags::INFO::SERIAL_NUMBER.into())?.as_string()?.to_string(),
+            mac_address: frame.get_item(tags::INFO::MAC_ADDRESS.into())?.as_string()?.to_string(),
+            sw_release: frame.get_item(tags::INFO::SW_RELEASE.into())?.as_string()?.to_string(),
+            production_date: frame.get_item(tags::INFO::PRODUCTION_DATE.into())?.as_string()?.to_string(),
+            platform_type: frame.get_item(tags::INFO::PLATFORM_TYPE.into())?.as_i32()?,
+        })
+    }
+}
+
+/// Usage of the device file system
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct FsUsage {
+    /// size of the file system
+    pub size: f64,
+
+    /// used space of the file system
+    pub used: f64,
+
+    /// available space of the file system
+    pub available: f64,
+
+    /// used space in percent
+    pub use_percent: f64,
+
+    /// number of inodes of the file system
+    pub inodes: f64,
+
+    /// number of used inodes
+    pub inodes_used: f64,
+
+    /// number of available inodes
+    pub inodes_available: f64,
+
+    /// used inodes in percent
+    pub inodes_use_percent: f64,
+}
+
+impl FsUsage {
+    /// Returns usage parsed from the `GET_FS_USAGE` container of the frame
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - the response frame
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rscp::info::{self, FsUsage};
+    /// let mut c = rscp::Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    /// c.connect("energy.storage.local", None).unwrap();
+    /// let usage = FsUsage::from_frame(&c.send_receive_frame(&info::request_fs_usage()).unwrap()).unwrap();
+    /// println!("{} % used", usage.use_percent);
+    /// ```
+    pub fn from_frame(frame: &Frame) -> Result<Self> {
+        let container = frame.get_item(tags::INFO::GET_FS_USAGE.into())?;
+        Ok(Self {
+            size: container.get_item(tags::INFO::FS_SIZE.into())?.as_f64_lossy()?,
+            used: container.get_item(tags::INFO::FS_USED.into())?.as_f64_lossy()?,
+            available: container.get_item(tags::INFO::FS_AVAILABLE.into())?.as_f64_lossy()?,
+            use_percent: container.get_item(tags::INFO::FS_USE_PERCENT.into())?.as_f64_lossy()?,
+            inodes: container.get_item(tags::INFO::INODES.into())?.as_f64_lossy()?,
+            inodes_used: container.get_item(tags::INFO::INODES_USED.into())?.as_f64_lossy()?,
+            inodes_available: container.get_item(tags::INFO::INODES_AVAILABLE.into())?.as_f64_lossy()?,
+            inodes_use_percent: container.get_item(tags::INFO::INODES_USE_PERCENT.into())?.as_f64_lossy()?,
+        })
+    }
+}
+
+/// Returns frame requesting the file system usage
+pub fn request_fs_usage() -> Frame {
+    let mut frame = Frame::new();
+    frame.push_item(Item::from_tag(tags::INFO::GET_FS_USAGE));
+    frame
+}
+
+/// Returns item to set the ip address of the device
+///
+/// # Arguments
+///
+/// * `ip` - the new ip address
+///
+/// # Examples
+///
+/// ```
+/// use std::net::Ipv4Addr;
+/// let item = rscp::info::set_ip_address(Ipv4Addr::new(192, 168, 1, 10));
+/// ```
+pub fn set_ip_address(ip: Ipv4Addr) -> Item {
+    Item::new_tag(tags::INFO::SET_IP_ADDRESS, ip.to_string())
+}
+
+/// Returns item to set the subnet mask of the device
+///
+/// # Arguments
+///
+/// * `mask` - the new subnet mask
+pub fn set_subnet_mask(mask: Ipv4Addr) -> Item {
+    Item::new_tag(tags::INFO::SET_SUBNET_MASK, mask.to_string())
+}
+
+/// Returns item to set the gateway of the device
+///
+/// # Arguments
+///
+/// * `gateway` - the new gateway address
+pub fn set_gateway(gateway: Ipv4Addr) -> Item {
+    Item::new_tag(tags::INFO::SET_GATEWAY, gateway.to_string())
+}
+
+/// Returns item to set the dns server of the device
+///
+/// # Arguments
+///
+/// * `dns` - the new dns server address
+pub fn set_dns(dns: Ipv4Addr) -> Item {
+    Item::new_tag(tags::INFO::SET_DNS, dns.to_string())
+}
+
+/// Returns item to enable or disable dhcp on the device
+///
+/// # Arguments
+///
+/// * `enabled` - true to enable dhcp
+pub fn set_dhcp_status(enabled: bool) -> Item {
+    Item::new_tag(tags::INFO::SET_DHCP_STATUS, enabled)
+}
+
+/// Returns item to set the time of the device
+///
+/// # Arguments
+///
+/// * `date_time` - the new time
+///
+/// # Examples
+///
+/// ```
+/// use chrono::Utc;
+/// let item = rscp::info::set_time(Utc::now());
+/// ```
+pub fn set_time(date_time: DateTime<Utc>) -> Item {
+    Item::new_tag(tags::INFO::SET_TIME, date_time)
+}
+
+/// Returns item to set the time zone of the device
+///
+/// # Arguments
+///
+/// * `time_zone` - the time zone name, e.g. `Europe/Berlin`
+pub fn set_time_zone(time_zone: &str) -> Item {
+    Item::new_tag(tags::INFO::SET_TIME_ZONE, time_zone.to_string())
+}
+
+/// ################################################
+///      TEST TEST TEST
+/// ################################################
+
+#[test]
+fn test_set_ip_address() {
+    let mut buffer: std::io::Cursor<Vec<u8>> = std::io::Cursor::new(Vec::new());
+    set_ip_address(Ipv4Addr::new(10, 0, 0, 1)).write_bytes(&mut buffer).unwrap();
+    assert_eq!(buffer.get_ref().to_vec(), vec![0x12, 0x00, 0x00, 0x0a, 0x0d, 0x08, 0x00, 0x31, 0x30, 0x2e, 0x30, 0x2e, 0x30, 0x2e, 0x31]);
+}
+
+#[test]
+fn test_set_time() {
+    let mut buffer: std::io::Cursor<Vec<u8>> = std::io::Cursor::new(Vec::new());
+    set_time(DateTime::<Utc>::from_timestamp(12345678, 123456).unwrap()).write_bytes(&mut buffer).unwrap();
+    assert_eq!(buffer.get_ref().to_vec(), vec![0x17, 0x00, 0x00, 0x0a, 0x0f, 0x0c, 0x00, 78, 97, 188, 0, 0, 0, 0, 0, 64, 226, 1, 0]);
+}
+
+#[test]
+fn test_set_dhcp_status() {
+    let mut buffer: std::io::Cursor<Vec<u8>> = std::io::Cursor::new(Vec::new());
+    set_dhcp_status(true).write_bytes(&mut buffer).unwrap();
+    assert_eq!(buffer.get_ref().to_vec(), vec![0x14, 0x00, 0x00, 0x0a, 0x01, 0x01, 0x00, 0x01]);
+}
+
+#[test]
+fn test_device_info_from_frame() {
+    let request = DeviceInfo::request();
+    assert!(request.get_item(tags::INFO::SERIAL_NUMBER.into()).unwrap().data.is_none());
+    assert!(request.get_item(tags::INFO::PLATFORM_TYPE.into()).unwrap().data.is_none());
+
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::INFO::SERIAL_NUMBER, "S10-123456789".to_string()));
+    frame.push_item(Item::new_tag(tags::INFO::MAC_ADDRESS, "00:11:22:33:44:55".to_string()));
+    frame.push_item(Item::new_tag(tags::INFO::SW_RELEASE, "S10_2024_01".to_string()));
+    frame.push_item(Item::new_tag(tags::INFO::PRODUCTION_DATE, "KW 12 2020".to_string()));
+    frame.push_item(Item::new_tag(tags::INFO::PLATFORM_TYPE, 3i32));
+    assert_eq!(DeviceInfo::from_frame(&frame).unwrap(), DeviceInfo {
+        serial_number: "S10-123456789".to_string(),
+        mac_address: "00:11:22:33:44:55".to_string(),
+        sw_release: "S10_2024_01".to_string(),
+        production_date: "KW 12 2020".to_string(),
+        platform_type: 3,
+    });
+
+    assert!(DeviceInfo::from_frame(&request).is_err());
+}
+
+#[test]
+fn test_fs_usage_from_frame() {
+    assert!(request_fs_usage().get_item(tags::INFO::GET_FS_USAGE.into()).unwrap().data.is_none());
+
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::INFO::GET_FS_USAGE, vec![
+        Item::new_tag(tags::INFO::FS_SIZE, 1073741824.0f64),
+        Item::new_tag(tags::INFO::FS_USED, 805306368.0f64),
+        Item::new_tag(tags::INFO::FS_AVAILABLE, 268435456.0f64),
+        Item::new_tag(tags::INFO::FS_USE_PERCENT, 75.0f32),
+        Item::new_tag(tags::INFO::INODES, 65536u64),
+        Item::new_tag(tags::INFO::INODES_USED, 16384u32),
+        Item::new_tag(tags::INFO::INODES_AVAILABLE, 49152.0f64),
+        Item::new_tag(tags::INFO::INODES_USE_PERCENT, 25.0f32),
+    ]));
+    assert_eq!(FsUsage::from_frame(&frame).unwrap(), FsUsage {
+        size: 1073741824.0,
+        used: 805306368.0,
+        available: 268435456.0,
+        use_percent: 75.0,
+        inodes: 65536.0,
+        inodes_used: 16384.0,
+        inodes_available: 49152.0,
+        inodes_use_percent: 25.0,
+    });
+
+    // the inode counts are required too
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::INFO::GET_FS_USAGE, vec![
+        Item::new_tag(tags::INFO::FS_SIZE, 1073741824.0f64),
+        Item::new_tag(tags::INFO::FS_USED, 805306368.0f64),
+        Item::new_tag(tags::INFO::FS_AVAILABLE, 268435456.0f64),
+        Item::new_tag(tags::INFO::FS_USE_PERCENT, 75.0f32),
+    ]));
+    assert!(FsUsage::from_frame(&frame).is_err());
+
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::INFO::GET_FS_USAGE, vec![Item::new_tag(tags::INFO::FS_SIZE, "1G".to_string())]));
+    assert_eq!(FsUsage::from_frame(&frame).unwrap_err().to_string(), "Type mismatch, expected Double64 got String");
+
+    assert!(FsUsage::from_frame(&request_fs_usage()).is_err());
+}
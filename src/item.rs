@@ -1,19 +1,54 @@
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, Result};
 use chrono::{DateTime, Utc};
+use log::trace;
 use std::any::{Any, TypeId};
 use std::fmt::Debug;
 use std::io::{Read, Write};
 use std::mem;
 
 use crate::read_ext::ReadExt;
-use crate::tags::TagGroup;
-use crate::{ErrorCode, GetItem};
+use crate::tags::{self, TagGroup};
+use crate::{ErrorCode, Errors, GetItem, ItemContainer, Weekdays};
 
 /// Site of item header - tag: 4, type: 1, length; 2
-const ITEM_HEADER_SIZE: u16 = 7;
+pub(crate) const ITEM_HEADER_SIZE: u16 = 7;
 
 /// bitmask to drop response bit
-const TAG_MASK: u32 = 0xff7fffff;
+pub(crate) const TAG_MASK: u32 = 0xff7fffff;
+
+/// default maximum nesting depth of containers when reading items
+pub const DEFAULT_MAX_NESTING_DEPTH: usize = 32;
+
+/// expected data types of well known tags
+const EXPECTED_TYPES: [(u32, DataType); 19] = [
+    (tags::RSCP::AUTHENTICATION_USER as u32, DataType::String),
+    (tags::RSCP::AUTHENTICATION_PASSWORD as u32, DataType::String),
+    (tags::RSCP::USER_LEVEL as u32, DataType::UChar8),
+    (tags::EMS::POWER_PV as u32, DataType::Int32),
+    (tags::EMS::POWER_BAT as u32, DataType::Int32),
+    (tags::EMS::POWER_HOME as u32, DataType::Int32),
+    (tags::EMS::POWER_GRID as u32, DataType::Int32),
+    (tags::EMS::POWER_ADD as u32, DataType::Int32),
+    (tags::EMS::AUTARKY as u32, DataType::Float32),
+    (tags::EMS::SELF_CONSUMPTION as u32, DataType::Float32),
+    (tags::EMS::BAT_SOC as u32, DataType::UChar8),
+    (tags::EMS::COUPLING_MODE as u32, DataType::UChar8),
+    (tags::EMS::POWERSAVE_ENABLED as u32, DataType::Bool),
+    (tags::INFO::SERIAL_NUMBER as u32, DataType::String),
+    (tags::INFO::PRODUCTION_DATE as u32, DataType::String),
+    (tags::INFO::IP_ADDRESS as u32, DataType::String),
+    (tags::INFO::MAC_ADDRESS as u32, DataType::String),
+    (tags::INFO::SW_RELEASE as u32, DataType::String),
+    (tags::INFO::TIME as u32, DataType::Timestamp),
+];
+
+/// tags with credentials, the data is redacted in debug and trace output
+const SENSITIVE_TAGS: [u32; 4] = [
+    tags::RSCP::AUTHENTICATION_USER as u32,
+    tags::RSCP::AUTHENTICATION_PASSWORD as u32,
+    tags::SERVER::USER as u32,
+    tags::SERVER::PASSWD as u32,
+];
 
 macro_rules! data_type_ext {
     (
@@ -99,6 +134,506 @@ impl Item {
         }
     }
 
+    /// Returns a container item with the sub items added by the closure
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - u32 representation of RSCP Protocol Tag
+    /// * `build` - closure adding the sub items to the builder
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{tags, Item};
+    /// let item = Item::container(tags::EMS::IDLE_PERIOD.into(), |c| {
+    ///     c.item(Item::new(tags::EMS::IDLE_PERIOD_DAY.into(), 0u8));
+    ///     c.container(tags::EMS::IDLE_PERIOD_START.into(), |c| {
+    ///         c.item(Item::new(tags::EMS::IDLE_PERIOD_HOUR.into(), 8u8));
+    ///         c.item(Item::new(tags::EMS::IDLE_PERIOD_MINUTE.into(), 30u8));
+    ///     });
+    /// });
+    /// ```
+    pub fn container(tag: u32, build: impl FnOnce(&mut ContainerBuilder)) -> Self {
+        let mut builder = ContainerBuilder::default();
+        build(&mut builder);
+        Self::new(tag, builder.items)
+    }
+
+    /// Returns a data item with float data, fails for NaN and infinite values which are rejected by the device
+    ///
+    /// Use `Item::new` to send non-finite values anyway.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - u32 representation of RSCP Protocol Tag
+    /// * `data` - the float value, `f32` or `f64`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{tags, Item};
+    /// let item = Item::float_checked(tags::EMS::AUTARKY.into(), 85.5f32).unwrap();
+    /// assert!(Item::float_checked(tags::EMS::AUTARKY.into(), f32::NAN).is_err());
+    /// ```
+    ///
+    /// Integers are not accepted:
+    ///
+    /// ```compile_fail
+    /// use rscp::{tags, Item};
+    /// let item = Item::float_checked(tags::EMS::AUTARKY.into(), 85u32);
+    /// ```
+    pub fn float_checked<T: float::Float>(tag: u32, data: T) -> Result<Self> {
+        if !data.into().is_finite() {
+            return Err(anyhow!(Errors::InvalidValue(format!("Non-finite float {} for tag {:#010x}", data.into(), tag))));
+        }
+        Ok(Self::new(tag, data))
+    }
+
+    /// Returns a data item with the duration encoded as u32 seconds, the fraction of a second is truncated
+    ///
+    /// Fails for durations exceeding `u32::MAX` seconds.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - u32 representation of RSCP Protocol Tag
+    /// * `duration` - the duration
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use rscp::{tags, Item};
+    /// let item = Item::from_duration(tags::EMS::EP_DELAY.into(), Duration::from_secs(90)).unwrap();
+    /// assert_eq!(item.as_u32().unwrap(), 90);
+    /// ```
+    pub fn from_duration(tag: u32, duration: std::time::Duration) -> Result<Self> {
+        match u32::try_from(duration.as_secs()) {
+            Ok(seconds) => Ok(Self::new(tag, seconds)),
+            Err(_) => Err(anyhow!(Errors::InvalidValue(format!("Duration of {} seconds exceeds u32 for tag {:#010x}", duration.as_secs(), tag)))),
+        }
+    }
+
+    /// Returns a data item with the chrono duration encoded as u32 seconds, see `from_duration`
+    ///
+    /// Fails for negative durations.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - u32 representation of RSCP Protocol Tag
+    /// * `duration` - the duration
+    pub fn from_chrono_duration(tag: u32, duration: chrono::Duration) -> Result<Self> {
+        match duration.to_std() {
+            Ok(duration) => Self::from_duration(tag, duration),
+            Err(_) => Err(anyhow!(Errors::InvalidValue(format!("Negative duration {} for tag {:#010x}", duration, tag)))),
+        }
+    }
+
+    /// Returns a data item without data content from a tag enum
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - RSCP Protocol Tag enum
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{tags, Item};
+    /// let item = Item::from_tag(tags::INFO::SERIAL_NUMBER);
+    /// ```
+    pub fn from_tag<G: Into<u32>>(tag: G) -> Self {
+        Self {
+            tag: tag.into(),
+            data: None,
+        }
+    }
+
+    /// Returns a data item using tag enum and any data element
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - RSCP Protocol Tag enum
+    /// * `data` - Any data content
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{tags, Item};
+    /// let item = Item::new_tag(tags::RSCP::AUTHENTICATION_USER, "username".to_string());
+    /// ```
+    pub fn new_tag<G: Into<u32>, T: Any>(tag: G, data: T) -> Self {
+        Self::new(tag.into(), data)
+    }
+
+    /// Returns a byte array item, the data can be given as slice, array or vector
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - RSCP Protocol Tag
+    /// * `data` - the bytes
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{tags, Item};
+    /// let data: &[u8] = &[0x01, 0x02, 0x03];
+    /// let item = Item::bytes(tags::INFO::MAC_ADDRESS.into(), data);
+    /// assert_eq!(item.as_bytes().unwrap(), data);
+    /// ```
+    pub fn bytes(tag: u32, data: impl Into<Vec<u8>>) -> Self {
+        Self::new(tag, data.into())
+    }
+
+    /// Returns true if both items have the same tag, the response bit is ignored
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - the item to compare with
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{tags, Item};
+    /// let request = Item::from_tag(tags::EMS::POWER_PV);
+    /// let response = Item::new(tags::EMS::POWER_PV as u32 | 0x00800000, 1200i32);
+    /// assert!(request.same_tag(&response));
+    /// ```
+    pub fn same_tag(&self, other: &Item) -> bool {
+        tags::as_request(self.tag) == tags::as_request(other.tag)
+    }
+
+    /// Returns the size of the serialized item including the item header
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{tags, Item};
+    /// let item = Item::new(tags::RSCP::AUTHENTICATION_USER.into(), "user".to_string());
+    /// assert_eq!(item.serialized_len().unwrap(), 11);
+    /// ```
+    pub fn serialized_len(&self) -> Result<u16> {
+        get_container_size(std::slice::from_ref(self))
+    }
+
+    /// Returns the data as bool
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{tags, Item};
+    /// let item = Item::new(tags::EMS::POWERSAVE_ENABLED.into(), true);
+    /// assert_eq!(item.as_bool().unwrap(), true);
+    /// ```
+    pub fn as_bool(&self) -> Result<bool> {
+        Ok(*self.data_as::<bool>(DataType::Bool)?)
+    }
+
+    /// Returns the data as u8
+    pub fn as_u8(&self) -> Result<u8> {
+        Ok(*self.data_as::<u8>(DataType::UChar8)?)
+    }
+
+    /// Returns the data as u16
+    pub fn as_u16(&self) -> Result<u16> {
+        Ok(*self.data_as::<u16>(DataType::UInt16)?)
+    }
+
+    /// Returns the data as u32
+    pub fn as_u32(&self) -> Result<u32> {
+        Ok(*self.data_as::<u32>(DataType::UInt32)?)
+    }
+
+    /// Returns the data as i32
+    pub fn as_i32(&self) -> Result<i32> {
+        Ok(*self.data_as::<i32>(DataType::Int32)?)
+    }
+
+    /// Returns the data as f32
+    pub fn as_f32(&self) -> Result<f32> {
+        Ok(*self.data_as::<f32>(DataType::Float32)?)
+    }
+
+    /// Returns the data as f64
+    pub fn as_f64(&self) -> Result<f64> {
+        Ok(*self.data_as::<f64>(DataType::Double64)?)
+    }
+
+    /// Returns the data of any numeric type as f64, for tags the firmware sends with different
+    /// types, 64 bit integers above 2^53 lose precision
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{tags, Item};
+    /// let item = Item::new(tags::EMS::POWER_PV.into(), 1234i32);
+    /// assert_eq!(item.as_f64_lossy().unwrap(), 1234.0);
+    /// ```
+    pub fn as_f64_lossy(&self) -> Result<f64> {
+        match get_data_type(self.data.as_ref())? {
+            DataType::Char8 => Ok(*self.data_as::<i8>(DataType::Char8)? as f64),
+            DataType::UChar8 => Ok(*self.data_as::<u8>(DataType::UChar8)? as f64),
+            DataType::Int16 => Ok(*self.data_as::<i16>(DataType::Int16)? as f64),
+            DataType::UInt16 => Ok(*self.data_as::<u16>(DataType::UInt16)? as f64),
+            DataType::Int32 => Ok(*self.data_as::<i32>(DataType::Int32)? as f64),
+            DataType::UInt32 => Ok(*self.data_as::<u32>(DataType::UInt32)? as f64),
+            DataType::Int64 => Ok(*self.data_as::<i64>(DataType::Int64)? as f64),
+            DataType::UInt64 => Ok(*self.data_as::<u64>(DataType::UInt64)? as f64),
+            DataType::Float32 => Ok(*self.data_as::<f32>(DataType::Float32)? as f64),
+            _ => self.as_f64(),
+        }
+    }
+
+    /// Returns the data as raw state of the DC/DC converter, the values are not documented, see
+    /// `DCDC::STATE_AS_STRING` for a description
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{tags, Item};
+    /// let item = Item::new(tags::DCDC::STATE.into(), 3u8);
+    /// assert_eq!(item.as_dcdc_state().unwrap(), 3);
+    /// ```
+    pub fn as_dcdc_state(&self) -> Result<u8> {
+        self.as_u8()
+    }
+
+    /// Returns the data as raw state of the PV inverter, the values are not documented
+    pub fn as_pvi_state(&self) -> Result<u8> {
+        self.as_u8()
+    }
+
+    /// Returns the data as set of week days, the data is a bitmask byte or a bitfield with monday
+    /// as first bit
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::Weekday;
+    /// use rscp::{tags, Item};
+    /// let item = Item::new(tags::EMS::PERIOD_WEEKDAYS.into(), 0b0000_0011u8);
+    /// assert!(item.as_weekdays().unwrap().contains(Weekday::Tue));
+    /// ```
+    pub fn as_weekdays(&self) -> Result<Weekdays> {
+        if let Some(bits) = self.data.as_ref().and_then(|data| data.downcast_ref::<Vec<bool>>()) {
+            return Ok(Weekdays::from_bits(bits.iter().take(7).rev().fold(0, |mask, bit| mask << 1 | *bit as u8)));
+        }
+        Ok(Weekdays::from_bits(*self.data_as::<u8>(DataType::UChar8)?))
+    }
+
+    /// Returns the data as string
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{tags, Item};
+    /// let item = Item::new(tags::INFO::SERIAL_NUMBER.into(), "serial".to_string());
+    /// assert_eq!(item.as_string().unwrap(), "serial");
+    /// ```
+    pub fn as_string(&self) -> Result<&str> {
+        Ok(self.data_as::<String>(DataType::String)?)
+    }
+
+    /// Returns the data as list of sub items
+    pub fn as_container(&self) -> Result<&[Item]> {
+        Ok(self.data_as::<Vec<Item>>(DataType::Container)?)
+    }
+
+    /// Returns the typed data of all sub items of the container, fails on the first mismatching item
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{tags, Item};
+    /// let item = Item::new(tags::BAT::DCB_ALL_CELL_VOLTAGES.into(), vec![
+    ///     Item::new(tags::BAT::DCB_CELL_VOLTAGE.into(), 3.31f32),
+    ///     Item::new(tags::BAT::DCB_CELL_VOLTAGE.into(), 3.32f32),
+    /// ]);
+    /// assert_eq!(item.collect_data::<f32>().unwrap(), vec![3.31, 3.32]);
+    /// ```
+    pub fn collect_data<T: 'static + Clone>(&self) -> Result<Vec<T>> {
+        self.as_container()?
+            .iter()
+            .map(|item| match item.data.as_ref().and_then(|data| data.downcast_ref::<T>()) {
+                Some(value) => Ok(value.clone()),
+                None => Err(anyhow!(Errors::TypeMismatch {
+                    expected: get_type_id_data_type(TypeId::of::<T>()).unwrap_or(DataType::Error),
+                    actual: get_data_type(item.data.as_ref()).unwrap_or(DataType::Error),
+                })),
+            })
+            .collect()
+    }
+
+    /// Returns the data as byte array
+    pub fn as_bytes(&self) -> Result<&[u8]> {
+        Ok(self.data_as::<Vec<u8>>(DataType::ByteArray)?)
+    }
+
+    /// Returns the bitfield data packed into an integer, the first bit is the least significant
+    ///
+    /// Fails if the bitfield has more than 32 bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{tags, Item};
+    /// let item = Item::new(tags::EMS::STATUS.into(), vec![true, false, true, false, false, false, false, false]);
+    /// assert_eq!(item.as_bits_u32().unwrap(), 0b101);
+    /// ```
+    pub fn as_bits_u32(&self) -> Result<u32> {
+        let bits = self.data_as::<Vec<bool>>(DataType::Bitfield)?;
+        if bits.len() > 32 {
+            return Err(anyhow!(Errors::InvalidValue(format!("Bitfield with {} bits exceeds 32 bits", bits.len()))));
+        }
+        Ok(bits.iter().enumerate().filter(|(_, bit)| **bit).fold(0, |value, (index, _)| value | 1 << index))
+    }
+
+    /// Returns the byte array data as null terminated string, invalid UTF-8 sequences are replaced
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{tags, Item};
+    /// let item = Item::new(tags::INFO::SERIAL_NUMBER.into(), b"S10\0\0\0".to_vec());
+    /// assert_eq!(item.as_cstring().unwrap(), "S10");
+    /// ```
+    pub fn as_cstring(&self) -> Result<String> {
+        let bytes = self.as_bytes()?;
+        let end = bytes.iter().position(|byte| *byte == 0).unwrap_or(bytes.len());
+        Ok(String::from_utf8_lossy(&bytes[..end]).into_owned())
+    }
+
+    /// Returns the data as timestamp, integer values are interpreted as seconds since epoch
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::{DateTime, Utc};
+    /// use rscp::{tags, Item};
+    /// let item = Item::new(tags::INFO::TIME.into(), 1700000000i64);
+    /// assert_eq!(item.as_timestamp().unwrap(), DateTime::<Utc>::from_timestamp(1700000000, 0).unwrap());
+    /// ```
+    pub fn as_timestamp(&self) -> Result<DateTime<Utc>> {
+        if let Some(data) = self.data.as_ref() {
+            if let Some(date_time) = data.downcast_ref::<DateTime<Utc>>() {
+                return Ok(*date_time);
+            }
+            let seconds = match (data.downcast_ref::<i64>(), data.downcast_ref::<u64>()) {
+                (Some(seconds), _) => Some(*seconds),
+                (_, Some(seconds)) => Some(i64::try_from(*seconds)?),
+                _ => None,
+            };
+            if let Some(seconds) = seconds {
+                return DateTime::<Utc>::from_timestamp(seconds, 0).ok_or_else(|| anyhow!("Timestamp out of range"));
+            }
+        }
+        Ok(*self.data_as::<DateTime<Utc>>(DataType::Timestamp)?)
+    }
+
+    /// Returns the data as duration, unsigned integer values are interpreted as seconds
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use rscp::{tags, Item};
+    /// let item = Item::new(tags::EMS::EP_DELAY.into(), 90u32);
+    /// assert_eq!(item.as_duration().unwrap(), Duration::from_secs(90));
+    /// ```
+    pub fn as_duration(&self) -> Result<std::time::Duration> {
+        let data = self.data.as_ref();
+        if let Some(seconds) = data.and_then(|data| data.downcast_ref::<u8>()) {
+            return Ok(std::time::Duration::from_secs(*seconds as u64));
+        }
+        if let Some(seconds) = data.and_then(|data| data.downcast_ref::<u16>()) {
+            return Ok(std::time::Duration::from_secs(*seconds as u64));
+        }
+        if let Some(seconds) = data.and_then(|data| data.downcast_ref::<u64>()) {
+            return Ok(std::time::Duration::from_secs(*seconds));
+        }
+        Ok(std::time::Duration::from_secs(*self.data_as::<u32>(DataType::UInt32)? as u64))
+    }
+
+    /// Returns the data as chrono duration, see `as_duration`
+    pub fn as_chrono_duration(&self) -> Result<chrono::Duration> {
+        Ok(chrono::Duration::from_std(self.as_duration()?)?)
+    }
+
+    /// Returns reference to typed data or a type mismatch error
+    ///
+    /// # Arguments
+    ///
+    /// * `expected` - the data type matching `T`
+    fn data_as<T: 'static>(&self, expected: DataType) -> Result<&T> {
+        match self.data.as_ref().and_then(|data| data.downcast_ref::<T>()) {
+            Some(value) => Ok(value),
+            None => Err(anyhow!(Errors::TypeMismatch {
+                expected,
+                actual: get_data_type(self.data.as_ref()).unwrap_or(DataType::Error),
+            })),
+        }
+    }
+
+    /// Returns the serialized item with tag, type, length and data as byte vector
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{tags, Item};
+    /// let item = Item::new(tags::RSCP::AUTHENTICATION_USER.into(), "username".to_string());
+    /// let item_bytes = item.to_bytes().unwrap();
+    /// ```
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut buffer: Vec<u8> = Vec::new();
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    /// Returns the data as JSON value, containers and byte arrays are returned as arrays and timestamps as ISO-8601 string
+    ///
+    /// Items without data, unsupported data and non-finite floats are returned as `null`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{tags, Item};
+    /// let item = Item::new(tags::EMS::POWER_PV.into(), 1200i32);
+    /// assert_eq!(item.to_json(), serde_json::json!(1200));
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> serde_json::Value {
+        use serde_json::Value;
+
+        let data = self.data.as_ref();
+        match get_data_type(data).unwrap_or(DataType::None) {
+            DataType::Bool => json_data::<bool>(data),
+            DataType::Char8 => json_data::<i8>(data),
+            DataType::UChar8 => json_data::<u8>(data),
+            DataType::Int16 => json_data::<i16>(data),
+            DataType::UInt16 => json_data::<u16>(data),
+            DataType::Int32 => json_data::<i32>(data),
+            DataType::UInt32 => json_data::<u32>(data),
+            DataType::Int64 => json_data::<i64>(data),
+            DataType::UInt64 => json_data::<u64>(data),
+            DataType::Float32 => json_data::<f32>(data),
+            DataType::Double64 => json_data::<f64>(data),
+            DataType::Bitfield => json_data::<Vec<bool>>(data),
+            DataType::String => json_data::<String>(data),
+            DataType::Container => match self.as_container() {
+                Ok(items) => Value::Array(items.iter().map(Item::to_json).collect()),
+                Err(_) => Value::Null,
+            },
+            DataType::Timestamp => match self.as_timestamp() {
+                Ok(date_time) => Value::String(date_time.to_rfc3339()),
+                Err(_) => Value::Null,
+            },
+            DataType::ByteArray => json_data::<Vec<u8>>(data),
+            DataType::Error => match data.and_then(|d| d.downcast_ref::<ErrorCode>()) {
+                Some(error_code) => Value::String(format!("{:?}", error_code)),
+                None => Value::Null,
+            },
+            DataType::None => Value::Null,
+        }
+    }
+
     /// Writes data to write cursor
     ///
     /// # Arguments
@@ -112,17 +647,17 @@ impl Item {
     /// let item = Item::new(tags::RSCP::AUTHENTICATION_USER.into(), "username".to_string());
     /// item.write_bytes(&mut buffer)?;
     /// ```
-    fn write_bytes<W: Write>(&self, writer: &mut W) -> Result<()> {
+    pub(crate) fn write_bytes<W: Write>(&self, writer: &mut W) -> Result<()> {
         // write tag to buffer
-        writer.write(&self.tag.to_le_bytes())?;
+        writer.write_all(&self.tag.to_le_bytes())?;
 
         // get the current type of data and write to buffer
         let data_type = get_data_type(self.data.as_ref())?;
-        writer.write(&[data_type.into()])?;
+        writer.write_all(&[data_type.into()])?;
 
         // get the lenght of the data and write to buffer
         let data_length = get_data_length(&data_type, self.data.as_ref())?;
-        writer.write(&data_length.to_le_bytes())?;
+        writer.write_all(&data_length.to_le_bytes())?;
 
         // write the data to buffer
         write_data(writer, &data_type, self.data.as_ref())?;
@@ -147,52 +682,199 @@ impl Item {
     /// let item = Item::read_bytes(&mut buffer, &mut len);
     /// ```
     pub fn read_bytes<R: Read>(reader: &mut R, length: &mut u16) -> Result<Self> {
-        let tag = reader.read_le::<u32>()?;
-        let data_type = DataType::from(reader.read_le::<u8>()?);
-        let data_len = reader.read_le::<u16>()?;
+        Self::read_bytes_with(reader, length, false, DEFAULT_MAX_NESTING_DEPTH)
+    }
+
+    /// Returns a data item from read cursor, fails if containers are nested deeper than the limit
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - read cursor
+    /// * `length` - pointer to current size of remaining data, will be decremented by number of bytes processed
+    /// * `max_depth` - maximum number of nested containers
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use rscp::Item;
+    /// // container item with an empty container
+    /// let mut buffer: Cursor<Vec<u8>> = Cursor::new(vec![0x01, 0x00, 0x00, 0x00, 0x0e, 0x07, 0x00, 0x02, 0x00, 0x00, 0x00, 0x0e, 0x00, 0x00]);
+    /// let mut len: u16 = 14;
+    /// assert!(Item::read_bytes_with_max_depth(&mut buffer, &mut len, 1).is_err());
+    /// ```
+    pub fn read_bytes_with_max_depth<R: Read>(reader: &mut R, length: &mut u16, max_depth: usize) -> Result<Self> {
+        Self::read_bytes_with(reader, length, false, max_depth)
+    }
+
+    /// Returns a data item from read cursor, malformed items are replaced by a placeholder
+    ///
+    /// Items and container sub items with data not matching the declared type are returned as placeholder item with
+    /// `ErrorCode::Format` data and parsing resumes at the next item using the declared length,
+    /// so the sibling items are not lost. Fails only if the item header or declared data is
+    /// missing.
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - read cursor
+    /// * `length` - pointer to current size of remaining data, will be decremented by number of bytes processed
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use rscp::{ErrorCode, Item};
+    /// // string item with invalid UTF-8 data
+    /// let mut buffer: Cursor<Vec<u8>> = Cursor::new(vec![0x01, 0x00, 0x00, 0x0a, 0x0d, 0x01, 0x00, 0xff]);
+    /// let mut len: u16 = 8;
+    /// let item = Item::read_bytes_lenient(&mut buffer, &mut len).unwrap();
+    /// assert_eq!(item.data.unwrap().downcast_ref::<ErrorCode>(), Some(&ErrorCode::Format));
+    /// ```
+    pub fn read_bytes_lenient<R: Read>(reader: &mut R, length: &mut u16) -> Result<Self> {
+        Self::read_bytes_with(reader, length, true, DEFAULT_MAX_NESTING_DEPTH)
+    }
+
+    /// Returns a data item from read cursor, see `read_bytes` and `read_bytes_lenient`
+    fn read_bytes_with<R: Read>(reader: &mut R, length: &mut u16, lenient: bool, max_depth: usize) -> Result<Self> {
+        let (tag, data_type, data_len) = read_header(reader).map_err(|e| map_eof_error(e, "item header"))?;
+        let item_len = u32::from(data_len) + u32::from(ITEM_HEADER_SIZE);
+        if item_len > u32::from(*length) {
+            bail!(Errors::Parse(format!("Item length {} exceeds remaining length {}", item_len, length)))
+        }
+        let data = if lenient {
+            // read the declared data first to be able to resume at the next item
+            let mut buf = vec![0u8; data_len as usize];
+            reader.read_exact(&mut buf).map_err(|e| map_eof_error(e.into(), &format!("{:?}", data_type)))?;
+            read_data(&mut std::io::Cursor::new(buf), &data_type, data_len, true, max_depth).unwrap_or_else(|_| Some(Box::new(ErrorCode::Format)))
+        } else {
+            read_data(reader, &data_type, data_len, false, max_depth).map_err(|e| map_eof_error(e, &format!("{:?}", data_type)))?
+        };
+
+        // can not underflow, the item length is checked against the remaining length above
+        *length -= item_len as u16;
+
+        Ok(Self {
+            tag: tag & TAG_MASK,
+            data: data,
+        })
+    }
+}
+
+mod float {
+    /// Float types accepted by `Item::float_checked`, sealed to `f32` and `f64`
+    pub trait Float: std::any::Any + Copy + Into<f64> {}
+
+    impl Float for f32 {}
+    impl Float for f64 {}
+}
+
+/// Collects the sub items of a container, see `Item::container`
+#[derive(Default)]
+pub struct ContainerBuilder {
+    /// the added sub items
+    items: Vec<Item>,
+}
+
+impl ContainerBuilder {
+    /// Adds the item to the container
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - the data item
+    pub fn item(&mut self, item: Item) -> &mut Self {
+        self.items.push(item);
+        self
+    }
+
+    /// Adds a nested container with the sub items added by the closure
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - u32 representation of RSCP Protocol Tag
+    /// * `build` - closure adding the sub items to the builder
+    pub fn container(&mut self, tag: u32, build: impl FnOnce(&mut ContainerBuilder)) -> &mut Self {
+        self.items.push(Item::container(tag, build));
+        self
+    }
+}
+
+/// Reads item header from reader and returns tag, data type and data length
+///
+/// # Arguments
+///
+/// * `reader` - the reader
+fn read_header<R: Read>(reader: &mut R) -> Result<(u32, DataType, u16)> {
+    let tag = reader.read_le::<u32>()?;
+    let data_type = DataType::from(reader.read_le::<u8>()?);
+    let data_len = reader.read_le::<u16>()?;
+    Ok((tag, data_type, data_len))
+}
 
-        let data: Option<Box<dyn Any>> = match data_type {
-            DataType::None => None,
-            DataType::Bool => Some(Box::new(reader.read_le::<u8>()? == 0x01)),
-            DataType::Char8 => Some(Box::new(reader.read_le::<i8>()?)),
-            DataType::UChar8 => Some(Box::new(reader.read_le::<u8>()?)),
-            DataType::Int16 => Some(Box::new(reader.read_le::<i16>()?)),
-            DataType::UInt16 => Some(Box::new(reader.read_le::<u16>()?)),
-            DataType::Int32 => Some(Box::new(reader.read_le::<i32>()?)),
-            DataType::UInt32 => Some(Box::new(reader.read_le::<u32>()?)),
-            DataType::Int64 => Some(Box::new(reader.read_le::<i64>()?)),
-            DataType::UInt64 => Some(Box::new(reader.read_le::<u64>()?)),
-            DataType::Float32 => Some(Box::new(reader.read_le::<f32>()?)),
-            DataType::Double64 => Some(Box::new(reader.read_le::<f64>()?)),
-            DataType::Bitfield => Some(Box::new(read_bitfield(reader, data_len)?)),
-            DataType::String => {
-                let mut buf = vec![0u8; data_len as usize];
-                reader.read_exact(&mut buf)?;
-                Some(Box::new(String::from_utf8(buf)?))
+/// Reads item data from reader
+///
+/// # Arguments
+///
+/// * `reader` - the reader
+/// * `data_type` - type of data
+/// * `data_len` - length of data
+/// * `lenient` - replace malformed items of containers, see `Item::read_bytes_lenient`
+/// * `max_depth` - maximum number of nested containers
+fn read_data<R: Read>(reader: &mut R, data_type: &DataType, data_len: u16, lenient: bool, max_depth: usize) -> Result<Option<Box<dyn Any>>> {
+    let data: Option<Box<dyn Any>> = match data_type {
+        DataType::None => None,
+        DataType::Bool => Some(Box::new(reader.read_le::<u8>()? == 0x01)),
+        DataType::Char8 => Some(Box::new(reader.read_le::<i8>()?)),
+        DataType::UChar8 => Some(Box::new(reader.read_le::<u8>()?)),
+        DataType::Int16 => Some(Box::new(reader.read_le::<i16>()?)),
+        DataType::UInt16 => Some(Box::new(reader.read_le::<u16>()?)),
+        DataType::Int32 => Some(Box::new(reader.read_le::<i32>()?)),
+        DataType::UInt32 => Some(Box::new(reader.read_le::<u32>()?)),
+        DataType::Int64 => Some(Box::new(reader.read_le::<i64>()?)),
+        DataType::UInt64 => Some(Box::new(reader.read_le::<u64>()?)),
+        DataType::Float32 => Some(Box::new(reader.read_le::<f32>()?)),
+        DataType::Double64 => Some(Box::new(reader.read_le::<f64>()?)),
+        DataType::Bitfield => Some(Box::new(read_bitfield(reader, data_len)?)),
+        DataType::String => {
+            let mut buf = vec![0u8; data_len as usize];
+            reader.read_exact(&mut buf)?;
+            Some(Box::new(String::from_utf8(buf)?))
+        }
+        DataType::Container => {
+            // limit the recursion of crafted frames
+            if max_depth == 0 {
+                bail!(Errors::Parse("Max nesting depth exceeded".to_string()))
             }
-            DataType::Container => {
-                let mut items: Vec<Item> = Vec::new();
-                let mut container_size = data_len;
-                while container_size > 0 {
-                    items.push(Item::read_bytes(reader, &mut container_size)?);
+            let mut items: Vec<Item> = Vec::new();
+            let mut container_size = data_len;
+            while container_size > 0 {
+                if container_size < ITEM_HEADER_SIZE {
+                    bail!(Errors::Parse(format!("Container length mismatch, {} bytes left over", container_size)))
                 }
-                Some(Box::new(items))
-            }
-            DataType::Timestamp => Some(Box::new(read_timestamp(reader)?)),
-            DataType::ByteArray => {
-                let mut buf = vec![0u8; data_len as usize];
-                reader.read_exact(&mut buf)?;
-                Some(Box::new(buf))
+                items.push(Item::read_bytes_with(reader, &mut container_size, lenient, max_depth - 1)?);
             }
-            DataType::Error => Some(Box::new(ErrorCode::from(reader.read_le::<u32>()?))),
-        };
-
-        *length -= data_len + ITEM_HEADER_SIZE;
+            Some(Box::new(items))
+        }
+        DataType::Timestamp => Some(Box::new(read_timestamp(reader)?)),
+        DataType::ByteArray => {
+            let mut buf = vec![0u8; data_len as usize];
+            reader.read_exact(&mut buf)?;
+            Some(Box::new(buf))
+        }
+        DataType::Error => Some(Box::new(ErrorCode::from(reader.read_le::<u32>()?))),
+    };
+    Ok(data)
+}
 
-        Ok(Self {
-            tag: tag & TAG_MASK,
-            data: data,
-        })
+/// Maps unexpected end of data to a parse error
+///
+/// # Arguments
+///
+/// * `err` - the error to map
+/// * `part` - name of the part which was read
+fn map_eof_error(err: anyhow::Error, part: &str) -> anyhow::Error {
+    match err.downcast_ref::<std::io::Error>() {
+        Some(io_err) if io_err.kind() == std::io::ErrorKind::UnexpectedEof => anyhow!(Errors::Parse(format!("unexpected end of frame while reading {}", part))),
+        _ => err,
     }
 }
 
@@ -211,10 +893,26 @@ impl GetItem for Item {
     }
 }
 
+impl ItemContainer for Item {
+    fn items(&self) -> &[Item] {
+        self.data.items()
+    }
+}
+
+impl IntoIterator for Item {
+    type Item = Item;
+    type IntoIter = std::vec::IntoIter<Item>;
+
+    /// Returns the sub items of the container, empty if the data is not a container
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.and_then(|data| data.downcast::<Vec<Item>>().ok()).map_or_else(Vec::new, |items| *items).into_iter()
+    }
+}
+
 impl std::fmt::Debug for Item {
     fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
         let tag_group = TagGroup::from((&self.tag >> 24) as u8);
-        let data_debug = get_debug_data(self.data.as_ref());
+        let data_debug = get_item_debug_data(self);
 
         fmt.debug_struct("Item")
             .field("tag", &tag_group.tags(&self.tag & TAG_MASK))
@@ -224,30 +922,76 @@ impl std::fmt::Debug for Item {
 }
 
 impl Clone for Item {
+    /// Clones the item, unsupported or mismatching data is cloned as `None`
     fn clone(&self) -> Self {
-        let data_type = get_data_type(self.data.as_ref()).unwrap();
-        let clone = match data_type {
-            DataType::Bool => Self { tag: self.tag, data: Some(Box::new(self.data.get_data::<bool>().unwrap().clone())) },
-            DataType::Char8 => Self { tag: self.tag, data: Some(Box::new(self.data.get_data::<i8>().unwrap().clone())) },
-            DataType::UChar8 => Self { tag: self.tag, data: Some(Box::new(self.data.get_data::<u8>().unwrap().clone())) },
-            DataType::Int16 => Self { tag: self.tag, data: Some(Box::new(self.data.get_data::<i16>().unwrap().clone())) },
-            DataType::UInt16 => Self { tag: self.tag, data: Some(Box::new(self.data.get_data::<u16>().unwrap().clone())) },
-            DataType::Int32 => Self { tag: self.tag, data: Some(Box::new(self.data.get_data::<i32>().unwrap().clone())) },
-            DataType::UInt32 => Self { tag: self.tag, data: Some(Box::new(self.data.get_data::<u32>().unwrap().clone())) },
-            DataType::Int64 => Self { tag: self.tag, data: Some(Box::new(self.data.get_data::<i64>().unwrap().clone())) },
-            DataType::UInt64 => Self { tag: self.tag, data: Some(Box::new(self.data.get_data::<u64>().unwrap().clone())) },
-            DataType::Float32 => Self { tag: self.tag, data: Some(Box::new(self.data.get_data::<f32>().unwrap().clone())) },
-            DataType::Double64 => Self { tag: self.tag, data: Some(Box::new(self.data.get_data::<f64>().unwrap().clone())) },
-            DataType::Bitfield => Self { tag: self.tag, data: Some(Box::new(self.data.get_data::<Vec<bool>>().unwrap().clone())) },
-            DataType::String => Self { tag: self.tag, data: Some(Box::new(self.data.get_data::<String>().unwrap().clone())) },
-            DataType::Container => Self { tag: self.tag, data: Some(Box::new(self.data.get_data::<Vec<Item>>().unwrap().clone())) },
-            DataType::Timestamp => Self { tag: self.tag, data: Some(Box::new(self.data.get_data::<DateTime<Utc>>().unwrap().clone())) },
-            DataType::ByteArray => Self { tag: self.tag, data: Some(Box::new(self.data.get_data::<Vec<u8>>().unwrap().clone())) },
-            DataType::Error => Self { tag: self.tag, data: Some(Box::new(self.data.get_data::<ErrorCode>().unwrap().clone())) },
-            DataType::None => Self { tag: self.tag, data: None },
+        let data = self.data.as_ref();
+        let data = match get_data_type(data).unwrap_or(DataType::None) {
+            DataType::Bool => clone_data::<bool>(data),
+            DataType::Char8 => clone_data::<i8>(data),
+            DataType::UChar8 => clone_data::<u8>(data),
+            DataType::Int16 => clone_data::<i16>(data),
+            DataType::UInt16 => clone_data::<u16>(data),
+            DataType::Int32 => clone_data::<i32>(data),
+            DataType::UInt32 => clone_data::<u32>(data),
+            DataType::Int64 => clone_data::<i64>(data),
+            DataType::UInt64 => clone_data::<u64>(data),
+            DataType::Float32 => clone_data::<f32>(data),
+            DataType::Double64 => clone_data::<f64>(data),
+            DataType::Bitfield => clone_data::<Vec<bool>>(data),
+            DataType::String => clone_data::<String>(data),
+            DataType::Container => clone_data::<Vec<Item>>(data),
+            DataType::Timestamp => clone_data::<DateTime<Utc>>(data),
+            DataType::ByteArray => clone_data::<Vec<u8>>(data),
+            DataType::Error => clone_data::<ErrorCode>(data),
+            DataType::None => None,
         };
-        return clone;
+        Self { tag: self.tag, data }
+    }
+}
+
+/// helper function for Clone of Item, returns `None` if the data is not of type `T`
+fn clone_data<T: 'static + Clone>(data: Option<&Box<dyn Any>>) -> Option<Box<dyn Any>> {
+    data.and_then(|d| d.downcast_ref::<T>()).map(|d| Box::new(d.clone()) as Box<dyn Any>)
+}
+
+/// helper function for Item::to_json, returns `null` if the data is not of type `T`
+#[cfg(feature = "serde")]
+fn json_data<T: 'static + Clone + Into<serde_json::Value>>(data: Option<&Box<dyn Any>>) -> serde_json::Value {
+    data.and_then(|d| d.downcast_ref::<T>()).map_or(serde_json::Value::Null, |d| d.clone().into())
+}
+
+/// logs item with tag, type and value at trace level, container items are logged on separate lines
+///
+/// # Arguments
+///
+/// * `item` - the item to log
+/// * `depth` - nesting depth used for indentation
+pub(crate) fn trace_item(item: &Item, depth: usize) {
+    let tag_group = TagGroup::from((item.tag >> 24) as u8);
+    let tag_name = tag_group.tags(item.tag & TAG_MASK);
+    let indent = "  ".repeat(depth);
+    match get_data_type(item.data.as_ref()) {
+        Ok(DataType::Container) => {
+            trace!(target: crate::LOG_TARGET, "{}{} ({:#010x}) {:?}", indent, tag_name, item.tag, DataType::Container);
+            for sub_item in item.data.get_data::<Vec<Item>>().unwrap() {
+                trace_item(sub_item, depth + 1);
+            }
+        }
+        Ok(data_type) => trace!(target: crate::LOG_TARGET, "{}{} ({:#010x}) {:?} = {:?}", indent, tag_name, item.tag, data_type, get_item_debug_data(item)),
+        Err(_) => trace!(target: crate::LOG_TARGET, "{}{} ({:#010x}) invalid data type", indent, tag_name, item.tag),
+    }
+}
+
+/// helper function for std::fmt::Debug of Item, redacts the data of sensitive tags
+///
+/// # Arguments
+///
+/// * `item` - the item
+fn get_item_debug_data(item: &Item) -> Box<dyn Debug + '_> {
+    if item.data.is_some() && SENSITIVE_TAGS.contains(&(item.tag & TAG_MASK)) {
+        return Box::new("***");
     }
+    get_debug_data(item.data.as_ref())
 }
 
 /// helper function for std::fmt::Debug of Item
@@ -304,28 +1048,38 @@ pub fn get_data_length(data_type: &DataType, data: Option<&Box<dyn Any>>) -> Res
         DataType::UInt64 => Ok(mem::size_of::<u64>() as u16),
         DataType::Float32 => Ok(mem::size_of::<f32>() as u16),
         DataType::Double64 => Ok(mem::size_of::<f64>() as u16),
-        DataType::Bitfield => Ok(((data.unwrap().downcast_ref::<Vec<bool>>().unwrap().len() as f32) / 8.0).ceil() as u16),
-        DataType::String => Ok(data.unwrap().downcast_ref::<String>().unwrap().len() as u16),
+        DataType::Bitfield => checked_data_length(data.unwrap().downcast_ref::<Vec<bool>>().unwrap().len().div_ceil(8)),
+        DataType::String => checked_data_length(data.unwrap().downcast_ref::<String>().unwrap().len()),
         DataType::Container => Ok(get_container_size(data.unwrap().downcast_ref::<Vec<Item>>().unwrap())?),
         DataType::Timestamp => Ok((mem::size_of::<i64>() + mem::size_of::<i32>()) as u16),
-        DataType::ByteArray => Ok(data.unwrap().downcast_ref::<Vec<u8>>().unwrap().len() as u16),
+        DataType::ByteArray => checked_data_length(data.unwrap().downcast_ref::<Vec<u8>>().unwrap().len()),
         DataType::Error => Ok(mem::size_of::<u32>() as u16),
     }
 }
 
+/// returns the length of variable sized data or an error if it exceeds the 16 bit length field
+///
+/// # Arguments
+///
+/// * `length` - length of the data in bytes
+fn checked_data_length(length: usize) -> Result<u16> {
+    u16::try_from(length).map_err(|_| anyhow!(Errors::InvalidValue(format!("Data length {} exceeds maximum of {} bytes", length, u16::MAX))))
+}
+
 /// retuns the size of a item vector (Container)
 ///
 /// # Arguments
 ///
 /// * `items` - Vector of items
 fn get_container_size(items: &[Item]) -> Result<u16> {
-    let mut size: u16 = 0;
+    // sum up as usize, the size of the items can exceed the 16 bit length field
+    let mut size: usize = 0;
     for item in items {
-        size += ITEM_HEADER_SIZE;
+        size += ITEM_HEADER_SIZE as usize;
         let data_type = get_data_type(item.data.as_ref())?;
-        size += get_data_length(&data_type, item.data.as_ref())?;
+        size += get_data_length(&data_type, item.data.as_ref())? as usize;
     }
-    Ok(size)
+    checked_data_length(size)
 }
 
 /// retuns data type of Any
@@ -333,33 +1087,64 @@ fn get_container_size(items: &[Item]) -> Result<u16> {
 /// # Arguments
 ///
 /// * `data` - Any Option
-fn get_data_type(data: Option<&Box<dyn Any>>) -> Result<DataType> {
+pub(crate) fn get_data_type(data: Option<&Box<dyn Any>>) -> Result<DataType> {
     match data {
-        Some(p) => {
-            // double deref: first * removes ref to Box, second * removes ref from box to any
-            let actual_id = (&**p).type_id();
-            match actual_id {
-                x if x == TypeId::of::<bool>() => Ok(DataType::Bool),
-                x if x == TypeId::of::<i8>() => Ok(DataType::Char8),
-                x if x == TypeId::of::<u8>() => Ok(DataType::UChar8),
-                x if x == TypeId::of::<i16>() => Ok(DataType::Int16),
-                x if x == TypeId::of::<u16>() => Ok(DataType::UInt16),
-                x if x == TypeId::of::<i32>() => Ok(DataType::Int32),
-                x if x == TypeId::of::<u32>() => Ok(DataType::UInt32),
-                x if x == TypeId::of::<i64>() => Ok(DataType::Int64),
-                x if x == TypeId::of::<u64>() => Ok(DataType::UInt64),
-                x if x == TypeId::of::<f32>() => Ok(DataType::Float32),
-                x if x == TypeId::of::<f64>() => Ok(DataType::Double64),
-                x if x == TypeId::of::<Vec<bool>>() => Ok(DataType::Bitfield),
-                x if x == TypeId::of::<String>() => Ok(DataType::String),
-                x if x == TypeId::of::<Vec<Item>>() => Ok(DataType::Container),
-                x if x == TypeId::of::<DateTime<Utc>>() => Ok(DataType::Timestamp),
-                x if x == TypeId::of::<Vec<u8>>() => Ok(DataType::ByteArray),
-                x if x == TypeId::of::<ErrorCode>() => Ok(DataType::Error),
-                _ => Err(anyhow!("Invalid data type")),
+        // double deref: first * removes ref to Box, second * removes ref from box to any
+        Some(p) => get_type_id_data_type((&**p).type_id()),
+        None => Ok(DataType::None),
+    }
+}
+
+/// Returns the data type for the type id of the data
+///
+/// # Arguments
+///
+/// * `actual_id` - type id of the data
+pub(crate) fn get_type_id_data_type(actual_id: TypeId) -> Result<DataType> {
+    // ordered by frequency in typical frames, containers and common scalars first
+    match actual_id {
+        x if x == TypeId::of::<Vec<Item>>() => Ok(DataType::Container),
+        x if x == TypeId::of::<i32>() => Ok(DataType::Int32),
+        x if x == TypeId::of::<u32>() => Ok(DataType::UInt32),
+        x if x == TypeId::of::<f32>() => Ok(DataType::Float32),
+        x if x == TypeId::of::<u8>() => Ok(DataType::UChar8),
+        x if x == TypeId::of::<bool>() => Ok(DataType::Bool),
+        x if x == TypeId::of::<u16>() => Ok(DataType::UInt16),
+        x if x == TypeId::of::<String>() => Ok(DataType::String),
+        x if x == TypeId::of::<f64>() => Ok(DataType::Double64),
+        x if x == TypeId::of::<i16>() => Ok(DataType::Int16),
+        x if x == TypeId::of::<i8>() => Ok(DataType::Char8),
+        x if x == TypeId::of::<i64>() => Ok(DataType::Int64),
+        x if x == TypeId::of::<u64>() => Ok(DataType::UInt64),
+        x if x == TypeId::of::<ErrorCode>() => Ok(DataType::Error),
+        x if x == TypeId::of::<DateTime<Utc>>() => Ok(DataType::Timestamp),
+        x if x == TypeId::of::<Vec<u8>>() => Ok(DataType::ByteArray),
+        x if x == TypeId::of::<Vec<bool>>() => Ok(DataType::Bitfield),
+        _ => Err(anyhow!("Invalid data type")),
+    }
+}
+
+/// Collects (tag, expected, actual) of items with data type not matching the expected type, containers are checked recursively
+///
+/// Items without data or with error code are skipped.
+///
+/// # Arguments
+///
+/// * `items` - the items to check
+/// * `mismatches` - the list of mismatches
+pub(crate) fn validate_types(items: &[Item], mismatches: &mut Vec<(u32, DataType, DataType)>) {
+    for item in items {
+        let tag = item.tag & TAG_MASK;
+        let actual = get_data_type(item.data.as_ref()).unwrap_or(DataType::Error);
+        if actual == DataType::Container {
+            validate_types(item.data.get_data::<Vec<Item>>().unwrap(), mismatches);
+        } else if actual != DataType::None && actual != DataType::Error {
+            if let Some((_, expected)) = EXPECTED_TYPES.iter().find(|(expected_tag, _)| *expected_tag == tag) {
+                if *expected != actual {
+                    mismatches.push((tag, *expected, actual));
+                }
             }
         }
-        None => Ok(DataType::None),
     }
 }
 
@@ -375,28 +1160,28 @@ pub fn write_data<W: Write>(writer: &mut W, data_type: &DataType, data: Option<&
         match data_type {
             DataType::None => {},
             DataType::Bool => { if *p.downcast_ref::<bool>().unwrap() {
-                writer.write(&[0x01u8])?;
+                writer.write_all(&[0x01u8])?;
             } else {
-                writer.write(&[0x00u8])?;
+                writer.write_all(&[0x00u8])?;
             }},
-            DataType::Char8 => { writer.write(&p.downcast_ref::<i8>().unwrap().to_le_bytes())?; },
-            DataType::UChar8 => { writer.write(&p.downcast_ref::<u8>().unwrap().to_le_bytes())?; },
-            DataType::Int16 => { writer.write(&p.downcast_ref::<i16>().unwrap().to_le_bytes())?; },
-            DataType::UInt16 => { writer.write(&p.downcast_ref::<u16>().unwrap().to_le_bytes())?; },
-            DataType::Int32 => { writer.write(&p.downcast_ref::<i32>().unwrap().to_le_bytes())?; },
-            DataType::UInt32 => { writer.write(&p.downcast_ref::<u32>().unwrap().to_le_bytes())?; },
-            DataType::Int64 => { writer.write(&p.downcast_ref::<i64>().unwrap().to_le_bytes())?; },
-            DataType::UInt64 => { writer.write(&p.downcast_ref::<u64>().unwrap().to_le_bytes())?; },
-            DataType::Float32 => { writer.write(&p.downcast_ref::<f32>().unwrap().to_le_bytes())?; },
-            DataType::Double64 => { writer.write(&p.downcast_ref::<f64>().unwrap().to_le_bytes())?; },
+            DataType::Char8 => { writer.write_all(&p.downcast_ref::<i8>().unwrap().to_le_bytes())?; },
+            DataType::UChar8 => { writer.write_all(&p.downcast_ref::<u8>().unwrap().to_le_bytes())?; },
+            DataType::Int16 => { writer.write_all(&p.downcast_ref::<i16>().unwrap().to_le_bytes())?; },
+            DataType::UInt16 => { writer.write_all(&p.downcast_ref::<u16>().unwrap().to_le_bytes())?; },
+            DataType::Int32 => { writer.write_all(&p.downcast_ref::<i32>().unwrap().to_le_bytes())?; },
+            DataType::UInt32 => { writer.write_all(&p.downcast_ref::<u32>().unwrap().to_le_bytes())?; },
+            DataType::Int64 => { writer.write_all(&p.downcast_ref::<i64>().unwrap().to_le_bytes())?; },
+            DataType::UInt64 => { writer.write_all(&p.downcast_ref::<u64>().unwrap().to_le_bytes())?; },
+            DataType::Float32 => { writer.write_all(&p.downcast_ref::<f32>().unwrap().to_le_bytes())?; },
+            DataType::Double64 => { writer.write_all(&p.downcast_ref::<f64>().unwrap().to_le_bytes())?; },
             DataType::Bitfield => { write_bitfield(writer, p.downcast_ref::<Vec<bool>>().unwrap())?; },
-            DataType::String => { writer.write(&p.downcast_ref::<String>().unwrap().as_bytes())?; },
+            DataType::String => { writer.write_all(&p.downcast_ref::<String>().unwrap().as_bytes())?; },
             DataType::Container => { for item in data.unwrap().downcast_ref::<Vec<Item>>().unwrap() {
                 item.write_bytes(writer)?;
             }},
             DataType::Timestamp => { write_timestamp(writer, p.downcast_ref::<DateTime<Utc>>().unwrap())?; },
-            DataType::ByteArray => { writer.write(&p.downcast_ref::<Vec<u8>>().unwrap())?; },
-            DataType::Error => { writer.write(&(*p.downcast_ref::<ErrorCode>().unwrap() as u32).to_le_bytes())?; },
+            DataType::ByteArray => { writer.write_all(&p.downcast_ref::<Vec<u8>>().unwrap())?; },
+            DataType::Error => { writer.write_all(&(*p.downcast_ref::<ErrorCode>().unwrap() as u32).to_le_bytes())?; },
         }
     }
 
@@ -410,14 +1195,14 @@ pub fn write_data<W: Write>(writer: &mut W, data_type: &DataType, data: Option<&
 /// * `writer` - write cursor
 /// * `bits` - vector of bits
 fn write_bitfield<W: Write>(writer: &mut W, bits: &[bool]) -> Result<()> {
-    let mut bytes = vec![0u8; (bits.len() as f32 / 8.0).ceil() as usize];
+    let mut bytes = vec![0u8; bits.len().div_ceil(8)];
     for bit_index in 0..bits.len() {
         if bits[bit_index] {
             let byte_index = bit_index / 8;
             bytes[byte_index] |= 1 << (bit_index % 8);
         }
     }
-    writer.write(&bytes)?;
+    writer.write_all(&bytes)?;
     Ok(())
 }
 
@@ -433,9 +1218,10 @@ fn read_bitfield<R: Read>(reader: &mut R, data_len: u16) -> Result<Vec<bool>> {
 
     let mut bits = vec![false; data_len as usize * 8];
 
-    for byte_index in 0..data_len {
+    // index as usize, the bit index of long bitfields exceeds u16
+    for (byte_index, byte) in buf.iter().enumerate() {
         for bit_index in 0..8 {
-            bits[(byte_index * 8 + bit_index) as usize] = buf[byte_index as usize] & (1 << bit_index) != 0;
+            bits[byte_index * 8 + bit_index] = byte & (1 << bit_index) != 0;
         }
     }
     Ok(bits)
@@ -443,25 +1229,38 @@ fn read_bitfield<R: Read>(reader: &mut R, data_len: u16) -> Result<Vec<bool>> {
 
 /// Writes datetime to writer
 ///
+/// The timestamp is encoded as little endian i64 seconds since epoch followed by little endian
+/// i32 nanoseconds. Seconds are rounded towards negative infinity, so the nanoseconds are always
+/// positive, e.g. 1.5 seconds before epoch is written as -2 seconds and 500000000 nanoseconds.
+///
 /// # Arguments
 ///
 /// * `writer` - write cursor
 /// * `date_time` - the time to write
 pub fn write_timestamp<W: Write>(writer: &mut W, date_time: &DateTime<Utc>) -> Result<()> {
-    writer.write(&date_time.timestamp().to_le_bytes())?;
-    writer.write(&date_time.timestamp_subsec_nanos().to_le_bytes())?;
+    writer.write_all(&date_time.timestamp().to_le_bytes())?;
+    writer.write_all(&(date_time.timestamp_subsec_nanos() as i32).to_le_bytes())?;
     Ok(())
 }
 
 /// Reads datetime from reader
 ///
+/// Negative nanoseconds, as written by encoders rounding seconds towards zero, are normalized.
+///
 /// # Arguments
 ///
 /// * `reader` - the reader
 pub fn read_timestamp<R: Read>(reader: &mut R) -> Result<DateTime<Utc>> {
-    let seconds = reader.read_le::<i64>()?;
-    let nanos = reader.read_le::<u32>()?;
-    Ok(DateTime::<Utc>::from_timestamp(seconds, nanos).unwrap())
+    let mut seconds = reader.read_le::<i64>()?;
+    let mut nanos = reader.read_le::<i32>()? as i64;
+    if nanos < 0 {
+        seconds = seconds.checked_sub(1).ok_or_else(|| anyhow!(Errors::Parse("Timestamp out of range".to_string())))?;
+        nanos += 1_000_000_000;
+    }
+    if nanos >= 1_000_000_000 {
+        return Err(anyhow!(Errors::Parse(format!("Invalid timestamp nanoseconds, got {}", nanos))));
+    }
+    DateTime::<Utc>::from_timestamp(seconds, nanos as u32).ok_or_else(|| anyhow!(Errors::Parse("Timestamp out of range".to_string())))
 }
 
 /// ################################################
@@ -493,136 +1292,136 @@ macro_rules! test_data_cases {
                 data: None,
                 byte_data: vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
                 data_size: mem::size_of::<()>() as u16,
-                item_str: "Item { tag: \"RSCP_GENERAL_ERROR\", data: \"None\" }",
+                item_str: "Item { tag: \"RSCP_UNKNOWN(0x00000000)\", data: \"None\" }",
             },
             TestData {
                 data_type: DataType::Bool,
                 data: Some(Box::new(true)),
                 byte_data: vec![0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00, 0x01],
                 data_size: mem::size_of::<bool>() as u16,
-                item_str: "Item { tag: \"RSCP_GENERAL_ERROR\", data: true }",
+                item_str: "Item { tag: \"RSCP_UNKNOWN(0x00000000)\", data: true }",
             },
             TestData {
                 data_type: DataType::Bool,
                 data: Some(Box::new(false)),
                 byte_data: vec![0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00, 0x00],
                 data_size: mem::size_of::<bool>() as u16,
-                item_str: "Item { tag: \"RSCP_GENERAL_ERROR\", data: false }",
+                item_str: "Item { tag: \"RSCP_UNKNOWN(0x00000000)\", data: false }",
             },
             TestData {
                 data_type: DataType::Char8,
                 data: Some(Box::new(-1i8)),
                 byte_data: vec![0x00, 0x00, 0x00, 0x00, 0x02, 0x01, 0x00, 0xff],
                 data_size: mem::size_of::<i8>() as u16,
-                item_str: "Item { tag: \"RSCP_GENERAL_ERROR\", data: -1 }",
+                item_str: "Item { tag: \"RSCP_UNKNOWN(0x00000000)\", data: -1 }",
             },
             TestData {
                 data_type: DataType::UChar8,
                 data: Some(Box::new(1u8)),
                 byte_data: vec![0x00, 0x00, 0x00, 0x00, 0x03, 0x01, 0x00, 0x01],
                 data_size: mem::size_of::<u8>() as u16,
-                item_str: "Item { tag: \"RSCP_GENERAL_ERROR\", data: 1 }",
+                item_str: "Item { tag: \"RSCP_UNKNOWN(0x00000000)\", data: 1 }",
             },
             TestData {
                 data_type: DataType::Int16,
                 data: Some(Box::new(-1i16)),
                 byte_data: vec![0x00, 0x00, 0x00, 0x00, 0x04, 0x02, 0x00, 0xff, 0xff],
                 data_size: mem::size_of::<i16>() as u16,
-                item_str: "Item { tag: \"RSCP_GENERAL_ERROR\", data: -1 }",
+                item_str: "Item { tag: \"RSCP_UNKNOWN(0x00000000)\", data: -1 }",
             },
             TestData {
                 data_type: DataType::UInt16,
                 data: Some(Box::new(1u16)),
                 byte_data: vec![0x00, 0x00, 0x00, 0x00, 0x05, 0x02, 0x00, 0x01, 0x00],
                 data_size: mem::size_of::<u16>() as u16,
-                item_str: "Item { tag: \"RSCP_GENERAL_ERROR\", data: 1 }",
+                item_str: "Item { tag: \"RSCP_UNKNOWN(0x00000000)\", data: 1 }",
             },
             TestData {
                 data_type: DataType::Int32,
                 data: Some(Box::new(-1i32)),
                 byte_data: vec![0x00, 0x00, 0x00, 0x00, 0x06, 0x04, 0x00, 0xff, 0xff, 0xff, 0xff],
                 data_size: mem::size_of::<i32>() as u16,
-                item_str: "Item { tag: \"RSCP_GENERAL_ERROR\", data: -1 }",
+                item_str: "Item { tag: \"RSCP_UNKNOWN(0x00000000)\", data: -1 }",
             },
             TestData {
                 data_type: DataType::UInt32,
                 data: Some(Box::new(1u32)),
                 byte_data: vec![0x00, 0x00, 0x00, 0x00, 0x07, 0x04, 0x00, 0x01, 0x00, 0x00, 0x00],
                 data_size: mem::size_of::<u32>() as u16,
-                item_str: "Item { tag: \"RSCP_GENERAL_ERROR\", data: 1 }",
+                item_str: "Item { tag: \"RSCP_UNKNOWN(0x00000000)\", data: 1 }",
             },
             TestData {
                 data_type: DataType::Int64,
                 data: Some(Box::new(-1i64)),
                 byte_data: vec![0x00, 0x00, 0x00, 0x00, 0x08, 0x08, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
                 data_size: mem::size_of::<i64>() as u16,
-                item_str: "Item { tag: \"RSCP_GENERAL_ERROR\", data: -1 }",
+                item_str: "Item { tag: \"RSCP_UNKNOWN(0x00000000)\", data: -1 }",
             },
             TestData {
                 data_type: DataType::UInt64,
                 data: Some(Box::new(1u64)),
                 byte_data: vec![0x00, 0x00, 0x00, 0x00, 0x09, 0x08, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00],
                 data_size: mem::size_of::<u64>() as u16,
-                item_str: "Item { tag: \"RSCP_GENERAL_ERROR\", data: 1 }",
+                item_str: "Item { tag: \"RSCP_UNKNOWN(0x00000000)\", data: 1 }",
             },
             TestData {
                 data_type: DataType::Float32,
                 data: Some(Box::new(1.0f32)),
                 byte_data: vec![0x00, 0x00, 0x00, 0x00, 0x0a, 0x04, 0x00, 0x00, 0x00, 0x80, 0x3f],
                 data_size: mem::size_of::<f32>() as u16,
-                item_str: "Item { tag: \"RSCP_GENERAL_ERROR\", data: 1.0 }",
+                item_str: "Item { tag: \"RSCP_UNKNOWN(0x00000000)\", data: 1.0 }",
             },
             TestData {
                 data_type: DataType::Double64,
                 data: Some(Box::new(1.0f64)),
                 byte_data: vec![0x00, 0x00, 0x00, 0x00, 0x0b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xf0, 0x3f],
                 data_size: mem::size_of::<f64>() as u16,
-                item_str: "Item { tag: \"RSCP_GENERAL_ERROR\", data: 1.0 }",
+                item_str: "Item { tag: \"RSCP_UNKNOWN(0x00000000)\", data: 1.0 }",
             },
             TestData {
                 data_type: DataType::Bitfield,
                 data: Some(Box::new(vec![true, false, true, false, true, false, true, false, false, true, false, true, false, true, false, true])),
                 byte_data: vec![0x00, 0x00, 0x00, 0x00, 0x0c, 0x02, 0x00, 0x55, 0xaa],
                 data_size: 2,
-                item_str: "Item { tag: \"RSCP_GENERAL_ERROR\", data: [true, false, true, false, true, false, true, false, false, true, false, true, false, true, false, true] }",
+                item_str: "Item { tag: \"RSCP_UNKNOWN(0x00000000)\", data: [true, false, true, false, true, false, true, false, false, true, false, true, false, true, false, true] }",
             },
             TestData {
                 data_type: DataType::String,
                 data: Some(Box::new("Test".to_string())),
                 byte_data: vec![0x00, 0x00, 0x00, 0x00, 0x0d, 0x04, 0x00, 0x54, 0x65, 0x73, 0x74],
                 data_size: 4,
-                item_str: "Item { tag: \"RSCP_GENERAL_ERROR\", data: \"Test\" }",
+                item_str: "Item { tag: \"RSCP_UNKNOWN(0x00000000)\", data: \"Test\" }",
             },
             TestData {
                 data_type: DataType::Container,
                 data: Some(Box::new(vec![
-                    Item::new(crate::tags::RSCP::AUTHENTICATION_USER.into(), "user".to_string()),
-                    Item::new(crate::tags::RSCP::AUTHENTICATION_PASSWORD.into(), "pwd".to_string()),
+                    Item::new_tag(crate::tags::RSCP::AUTHENTICATION_USER, "user".to_string()),
+                    Item::new_tag(crate::tags::RSCP::AUTHENTICATION_PASSWORD, "pwd".to_string()),
                 ])),
                 byte_data: vec![0x00, 0x00, 0x00, 0x00, 0x0e, 0x15, 0x00, 2, 0, 0, 0, 13, 4, 0, 117, 115, 101, 114, 3, 0, 0, 0, 13, 3, 0, 112, 119, 100],
                 data_size: 21,
-                item_str: "Item { tag: \"RSCP_GENERAL_ERROR\", data: [Item { tag: \"RSCP_AUTHENTICATION_USER\", data: \"user\" }, Item { tag: \"RSCP_AUTHENTICATION_PASSWORD\", data: \"pwd\" }] }",
+                item_str: "Item { tag: \"RSCP_UNKNOWN(0x00000000)\", data: [Item { tag: \"RSCP_AUTHENTICATION_USER\", data: \"***\" }, Item { tag: \"RSCP_AUTHENTICATION_PASSWORD\", data: \"***\" }] }",
             },
             TestData {
                 data_type: DataType::Timestamp,
                 data: Some(Box::new(DateTime::<Utc>::from_timestamp(12345678, 123456).unwrap())),
                 byte_data: vec![0x00, 0x00, 0x00, 0x00, 0x0f, 0x0c, 0x00, 78, 97, 188, 0, 0, 0, 0, 0, 64, 226, 1, 0],
                 data_size: (mem::size_of::<i64>() + mem::size_of::<i32>()) as u16,
-                item_str: "Item { tag: \"RSCP_GENERAL_ERROR\", data: 1970-05-23T21:21:18.000123456Z }",
+                item_str: "Item { tag: \"RSCP_UNKNOWN(0x00000000)\", data: 1970-05-23T21:21:18.000123456Z }",
             },
             TestData {
                 data_type: DataType::ByteArray,
                 data: Some(Box::new(vec![0x0fu8; 4])),
                 byte_data: vec![0x00, 0x00, 0x00, 0x00, 0x10, 0x04, 0x00, 0x0f, 0x0f, 0x0f, 0x0f],
                 data_size: 4,
-                item_str: "Item { tag: \"RSCP_GENERAL_ERROR\", data: [15, 15, 15, 15] }",
+                item_str: "Item { tag: \"RSCP_UNKNOWN(0x00000000)\", data: [15, 15, 15, 15] }",
             },
             TestData {
                 data_type: DataType::Error,
                 data: Some(Box::new(ErrorCode::NotHandled)),
                 byte_data: vec![0x00, 0x00, 0x00, 0x00, 0xff, 0x04, 0x00, 0x01, 0x00, 0x00, 0x00],
                 data_size: mem::size_of::<u32>() as u16,
-                item_str: "Item { tag: \"RSCP_GENERAL_ERROR\", data: NotHandled }",
+                item_str: "Item { tag: \"RSCP_UNKNOWN(0x00000000)\", data: NotHandled }",
             },
         ];
         test_cases
@@ -652,11 +1451,342 @@ fn test_item_read_bytes() {
     }
 }
 
+#[test]
+fn test_new_from_tag_enum() {
+    let item = Item::from_tag(crate::tags::INFO::SERIAL_NUMBER);
+    assert_eq!(item.tag, crate::tags::INFO::SERIAL_NUMBER as u32);
+    assert!(item.data.is_none());
+
+    let item = Item::new_tag(crate::tags::RSCP::AUTHENTICATION_USER, "username".to_string());
+    assert_eq!(item.tag, crate::tags::RSCP::AUTHENTICATION_USER as u32);
+    assert_eq!(item.get_data::<String>().unwrap(), "username");
+}
+
+#[test]
+fn test_as_timestamp() {
+    let date_time = DateTime::<Utc>::from_timestamp(12345678, 123456).unwrap();
+    let item = Item::new_tag(crate::tags::INFO::UTC_TIME, date_time);
+    assert_eq!(item.as_timestamp().unwrap(), date_time);
+
+    let item = Item::new_tag(crate::tags::INFO::UTC_TIME, 12345678i64);
+    assert_eq!(item.as_timestamp().unwrap(), DateTime::<Utc>::from_timestamp(12345678, 0).unwrap());
+
+    let item = Item::new_tag(crate::tags::INFO::UTC_TIME, 12345678u64);
+    assert_eq!(item.as_timestamp().unwrap(), DateTime::<Utc>::from_timestamp(12345678, 0).unwrap());
+
+    let item = Item::new_tag(crate::tags::INFO::UTC_TIME, "12345678".to_string());
+    assert_eq!(item.as_timestamp().unwrap_err().to_string(), "Type mismatch, expected Timestamp got String");
+
+    let item = Item::from_tag(crate::tags::INFO::UTC_TIME);
+    assert_eq!(item.as_timestamp().unwrap_err().to_string(), "Type mismatch, expected Timestamp got None");
+}
+
+#[test]
+fn test_duration() {
+    use std::time::Duration;
+
+    let item = Item::from_duration(crate::tags::EMS::EP_DELAY.into(), Duration::from_millis(90_999)).unwrap();
+    assert_eq!(item.get_data::<u32>().unwrap(), &90);
+    assert_eq!(item.as_duration().unwrap(), Duration::from_secs(90));
+    assert_eq!(item.as_chrono_duration().unwrap(), chrono::Duration::seconds(90));
+
+    let item = Item::from_chrono_duration(crate::tags::EMS::EP_DELAY.into(), chrono::Duration::minutes(5)).unwrap();
+    assert_eq!(item.get_data::<u32>().unwrap(), &300);
+    assert_eq!(item.as_duration().unwrap(), Duration::from_secs(300));
+
+    let err = Item::from_duration(crate::tags::EMS::EP_DELAY.into(), Duration::from_secs(u32::MAX as u64 + 1)).unwrap_err();
+    assert_eq!(err.to_string(), "Invalid value: Duration of 4294967296 seconds exceeds u32 for tag 0x01000203");
+    assert!(Item::from_chrono_duration(crate::tags::EMS::EP_DELAY.into(), chrono::Duration::seconds(-1)).is_err());
+
+    let item = Item::new_tag(crate::tags::EMS::EP_DELAY, 60u16);
+    assert_eq!(item.as_duration().unwrap(), Duration::from_secs(60));
+    let item = Item::new_tag(crate::tags::EMS::EP_DELAY, 60u64);
+    assert_eq!(item.as_duration().unwrap(), Duration::from_secs(60));
+    let item = Item::new_tag(crate::tags::EMS::EP_DELAY, -60i32);
+    assert_eq!(item.as_duration().unwrap_err().to_string(), "Type mismatch, expected UInt32 got Int32");
+}
+
+#[test]
+fn test_float_checked() {
+    let item = Item::float_checked(crate::tags::EMS::AUTARKY.into(), 85.5f32).unwrap();
+    assert_eq!(item.as_f32().unwrap(), 85.5);
+    let item = Item::float_checked(crate::tags::EMS::AUTARKY.into(), 85.5f64).unwrap();
+    assert_eq!(item.as_f64().unwrap(), 85.5);
+
+    let err = Item::float_checked(crate::tags::EMS::AUTARKY.into(), f32::NAN).unwrap_err();
+    assert_eq!(format!("{}", err), "Invalid value: Non-finite float NaN for tag 0x01000006");
+    assert!(Item::float_checked(crate::tags::EMS::AUTARKY.into(), f64::INFINITY).is_err());
+    assert!(Item::float_checked(crate::tags::EMS::AUTARKY.into(), f32::NEG_INFINITY).is_err());
+}
+
+#[test]
+fn test_as_accessors() {
+    let item = Item::new_tag(crate::tags::EMS::POWERSAVE_ENABLED, true);
+    assert!(item.as_bool().unwrap());
+    assert_eq!(item.as_u32().unwrap_err().to_string(), "Type mismatch, expected UInt32 got Bool");
+
+    let item = Item::new_tag(crate::tags::EMS::POWER_PV, 1234i32);
+    assert_eq!(item.as_i32().unwrap(), 1234);
+    assert_eq!(item.as_u32().unwrap_err().to_string(), "Type mismatch, expected UInt32 got Int32");
+
+    let item = Item::new_tag(crate::tags::EMS::POWER_PV, 1234u32);
+    assert_eq!(item.as_u32().unwrap(), 1234);
+    assert_eq!(item.as_u16().unwrap_err().to_string(), "Type mismatch, expected UInt16 got UInt32");
+
+    let item = Item::new_tag(crate::tags::BAT::INDEX, 3u16);
+    assert_eq!(item.as_u16().unwrap(), 3);
+    assert_eq!(item.as_u8().unwrap_err().to_string(), "Type mismatch, expected UChar8 got UInt16");
+
+    let item = Item::new_tag(crate::tags::EMS::BAT_SOC, 80u8);
+    assert_eq!(item.as_u8().unwrap(), 80);
+
+    let item = Item::new_tag(crate::tags::BAT::RSOC, 38.5f32);
+    assert_eq!(item.as_f32().unwrap(), 38.5);
+    assert_eq!(item.as_f64().unwrap_err().to_string(), "Type mismatch, expected Double64 got Float32");
+
+    let item = Item::new_tag(crate::tags::BAT::RSOC, 38.5f64);
+    assert_eq!(item.as_f64().unwrap(), 38.5);
+    assert_eq!(item.as_f64_lossy().unwrap(), 38.5);
+
+    assert_eq!(Item::new_tag(crate::tags::BAT::RSOC, 38.5f32).as_f64_lossy().unwrap(), 38.5);
+    assert_eq!(Item::new_tag(crate::tags::BAT::RSOC, -3i16).as_f64_lossy().unwrap(), -3.0);
+    assert_eq!(Item::new_tag(crate::tags::BAT::RSOC, 7u64).as_f64_lossy().unwrap(), 7.0);
+    let item = Item::new_tag(crate::tags::BAT::RSOC, "38.5".to_string());
+    assert_eq!(item.as_f64_lossy().unwrap_err().to_string(), "Type mismatch, expected Double64 got String");
+
+    let item = Item::new_tag(crate::tags::INFO::SERIAL_NUMBER, "serial".to_string());
+    assert_eq!(item.as_string().unwrap(), "serial");
+    assert_eq!(item.as_bytes().unwrap_err().to_string(), "Type mismatch, expected ByteArray got String");
+
+    let item = Item::new_tag(crate::tags::INFO::SERIAL_NUMBER, vec![0x01u8, 0x02]);
+    assert_eq!(item.as_bytes().unwrap(), [0x01, 0x02]);
+
+    let item = Item::new_tag(crate::tags::BAT::DATA, vec![Item::new_tag(crate::tags::BAT::INDEX, 0u16)]);
+    assert_eq!(item.as_container().unwrap().len(), 1);
+    assert_eq!(item.as_string().unwrap_err().downcast::<Errors>().unwrap().to_string(), "Type mismatch, expected String got Container");
+}
+
+#[test]
+fn test_collect_data() {
+    let item = Item::new_tag(crate::tags::BAT::DCB_ALL_CELL_VOLTAGES, vec![
+        Item::new_tag(crate::tags::BAT::DCB_CELL_VOLTAGE, 3.31f32),
+        Item::new_tag(crate::tags::BAT::DCB_CELL_VOLTAGE, 3.32f32),
+        Item::new_tag(crate::tags::BAT::DCB_CELL_VOLTAGE, 3.29f32),
+    ]);
+    assert_eq!(item.collect_data::<f32>().unwrap(), vec![3.31, 3.32, 3.29]);
+    assert_eq!(item.collect_data::<f64>().unwrap_err().to_string(), "Type mismatch, expected Double64 got Float32");
+
+    let empty = Item::new_tag(crate::tags::BAT::DCB_ALL_CELL_VOLTAGES, Vec::<Item>::new());
+    assert!(empty.collect_data::<f32>().unwrap().is_empty());
+
+    let scalar = Item::new_tag(crate::tags::BAT::DCB_CELL_VOLTAGE, 3.31f32);
+    assert_eq!(scalar.collect_data::<f32>().unwrap_err().to_string(), "Type mismatch, expected Container got Float32");
+}
+
+#[test]
+fn test_into_iter() {
+    let item = Item::new_tag(crate::tags::RSCP::AUTHENTICATION, vec![
+        Item::new_tag(crate::tags::RSCP::AUTHENTICATION_USER, "username".to_string()),
+        Item::new_tag(crate::tags::RSCP::AUTHENTICATION_PASSWORD, "password".to_string()),
+    ]);
+    let values: Vec<String> = item.into_iter().map(|sub_item| *sub_item.data.unwrap().downcast::<String>().unwrap()).collect();
+    assert_eq!(values, vec!["username".to_string(), "password".to_string()]);
+
+    assert_eq!(Item::new_tag(crate::tags::EMS::POWER_PV, 1200i32).into_iter().count(), 0);
+}
+
+#[test]
+fn test_bytes() {
+    let data: &[u8] = &[0xde, 0xad, 0xbe, 0xef];
+    let item = Item::bytes(crate::tags::INFO::MAC_ADDRESS.into(), data);
+    assert_eq!(item.to_bytes().unwrap(), vec![0x0a, 0x00, 0x00, 0x0a, 0x10, 0x04, 0x00, 0xde, 0xad, 0xbe, 0xef]);
+    assert_eq!(Item::bytes(crate::tags::INFO::MAC_ADDRESS.into(), [0xde, 0xad, 0xbe, 0xef]).as_bytes().unwrap(), data);
+    assert_eq!(Item::bytes(crate::tags::INFO::MAC_ADDRESS.into(), data.to_vec()).as_bytes().unwrap(), data);
+}
+
+#[test]
+fn test_as_weekdays() {
+    use chrono::Weekday;
+
+    let item = Item::new_tag(crate::tags::EMS::PERIOD_WEEKDAYS, 0b0100_0001u8);
+    assert_eq!(item.as_weekdays().unwrap().days(), vec![Weekday::Mon, Weekday::Sun]);
+
+    let item = Item::new_tag(crate::tags::MYPV::DEVICE_BOOST_WEEKDAYS, vec![false, true, false, false, false, true, true]);
+    assert_eq!(item.as_weekdays().unwrap().to_bits(), 0b0110_0010);
+
+    let item = Item::new_tag(crate::tags::EMS::PERIOD_WEEKDAYS, 1u32);
+    assert_eq!(item.as_weekdays().unwrap_err().to_string(), "Type mismatch, expected UChar8 got UInt32");
+}
+
+#[test]
+fn test_same_tag() {
+    let request = Item::from_tag(crate::tags::EMS::POWER_PV);
+    let response = Item::new(crate::tags::EMS::POWER_PV as u32 | 0x00800000, 1200i32);
+    assert!(request.same_tag(&response));
+    assert!(response.same_tag(&request));
+    assert!(request.same_tag(&request));
+    assert!(!request.same_tag(&Item::new(crate::tags::EMS::POWER_BAT as u32 | 0x00800000, 1200i32)));
+}
+
+#[test]
+fn test_as_bits_u32() {
+    let mut buffer: std::io::Cursor<Vec<u8>> = std::io::Cursor::new(vec![0b01010101, 0b10101010]);
+    let item = Item::new_tag(crate::tags::EMS::STATUS, read_bitfield(&mut buffer, 2).unwrap());
+    assert_eq!(item.as_bits_u32().unwrap(), 0b10101010_01010101);
+
+    let item = Item::new_tag(crate::tags::EMS::STATUS, vec![true; 32]);
+    assert_eq!(item.as_bits_u32().unwrap(), u32::MAX);
+
+    let item = Item::new_tag(crate::tags::EMS::STATUS, vec![false; 33]);
+    assert_eq!(item.as_bits_u32().unwrap_err().to_string(), "Invalid value: Bitfield with 33 bits exceeds 32 bits");
+
+    let item = Item::new_tag(crate::tags::EMS::STATUS, 5u32);
+    assert_eq!(item.as_bits_u32().unwrap_err().to_string(), "Type mismatch, expected Bitfield got UInt32");
+}
+
+#[test]
+fn test_item_read_bytes_lenient() {
+    let item = Item::new_tag(crate::tags::BAT::DATA, vec![
+        Item::new_tag(crate::tags::BAT::INDEX, 0u16),
+        Item::new_tag(crate::tags::BAT::DEVICE_NAME, "name".to_string()),
+        Item::new_tag(crate::tags::BAT::RSOC, 38.5f32),
+    ]);
+    let mut data = item.to_bytes().unwrap();
+    // invalid UTF-8 in the string of the middle item
+    let string_start = 7 + 9 + 7;
+    data[string_start] = 0xff;
+
+    let mut length = data.len() as u16;
+    assert!(Item::read_bytes(&mut std::io::Cursor::new(data.clone()), &mut length).is_err());
+
+    let mut length = data.len() as u16;
+    let item = Item::read_bytes_lenient(&mut std::io::Cursor::new(data.clone()), &mut length).unwrap();
+    assert_eq!(length, 0);
+    let items = item.as_container().unwrap();
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[0].get_data::<u16>().unwrap(), &0);
+    assert_eq!(items[1].tag, crate::tags::BAT::DEVICE_NAME as u32);
+    assert_eq!(items[1].get_data::<ErrorCode>().unwrap(), &ErrorCode::Format);
+    assert_eq!(items[2].as_f32().unwrap(), 38.5);
+
+    // the declared data must be available
+    let mut length = data.len() as u16;
+    let err = Item::read_bytes_lenient(&mut std::io::Cursor::new(data[..data.len() - 1].to_vec()), &mut length).unwrap_err();
+    assert_eq!(err.to_string(), "Frame parse error: unexpected end of frame while reading Container");
+}
+
+#[test]
+fn test_item_read_bytes_container_length() {
+    let item = Item::new_tag(crate::tags::BAT::DATA, vec![Item::new_tag(crate::tags::BAT::INDEX, 0u16)]);
+    let mut data = item.to_bytes().unwrap();
+    // declared container length one byte too large
+    data[5] += 1;
+    data.push(0x00);
+    let mut length = data.len() as u16;
+    let err = Item::read_bytes(&mut std::io::Cursor::new(data), &mut length).unwrap_err();
+    assert_eq!(err.to_string(), "Frame parse error: Container length mismatch, 1 bytes left over");
+
+    // declared container length one byte too small
+    let mut data = item.to_bytes().unwrap();
+    data[5] -= 1;
+    let mut length = data.len() as u16;
+    let err = Item::read_bytes(&mut std::io::Cursor::new(data), &mut length).unwrap_err();
+    assert_eq!(err.to_string(), "Frame parse error: Item length 9 exceeds remaining length 8");
+
+    let data = item.to_bytes().unwrap();
+    let mut length = data.len() as u16 - 1;
+    let err = Item::read_bytes(&mut std::io::Cursor::new(data), &mut length).unwrap_err();
+    assert_eq!(err.to_string(), "Frame parse error: Item length 16 exceeds remaining length 15");
+}
+
+#[test]
+fn test_item_read_bytes_max_depth() {
+    fn nested(depth: usize) -> Vec<u8> {
+        let mut item = Item::new_tag(crate::tags::EMS::POWER_PV, 1200i32);
+        for _ in 0..depth {
+            item = Item::new_tag(crate::tags::EMS::GET_SYS_SPECS, vec![item]);
+        }
+        item.to_bytes().unwrap()
+    }
+
+    let data = nested(DEFAULT_MAX_NESTING_DEPTH);
+    let mut length = data.len() as u16;
+    assert!(Item::read_bytes(&mut std::io::Cursor::new(data), &mut length).is_ok());
+
+    let data = nested(DEFAULT_MAX_NESTING_DEPTH + 1);
+    let mut length = data.len() as u16;
+    let err = Item::read_bytes(&mut std::io::Cursor::new(data), &mut length).unwrap_err();
+    assert_eq!(err.to_string(), "Frame parse error: Max nesting depth exceeded");
+
+    let data = nested(3);
+    let mut length = data.len() as u16;
+    assert!(Item::read_bytes_with_max_depth(&mut std::io::Cursor::new(data.clone()), &mut length, 2).is_err());
+    let mut length = data.len() as u16;
+    assert!(Item::read_bytes_with_max_depth(&mut std::io::Cursor::new(data), &mut length, 3).is_ok());
+
+    // deeply nested frame is rejected without exhausting the stack
+    let mut data = crate::Frame::new().without_checksum().to_bytes().unwrap();
+    let mut nested_data = Vec::new();
+    let depth = 9000usize;
+    for level in 0..depth {
+        let length = ((depth - level - 1) * 7) as u16;
+        nested_data.extend_from_slice(&(crate::tags::EMS::GET_SYS_SPECS as u32).to_le_bytes());
+        nested_data.push(DataType::Container as u8);
+        nested_data.extend_from_slice(&length.to_le_bytes());
+    }
+    data[16..18].copy_from_slice(&(nested_data.len() as u16).to_le_bytes());
+    data.extend_from_slice(&nested_data);
+    let err = crate::Frame::from_bytes(data).unwrap_err();
+    assert_eq!(err.to_string(), "Frame parse error: Max nesting depth exceeded");
+}
+
+#[test]
+fn test_as_cstring() {
+    let item = Item::new_tag(crate::tags::INFO::SERIAL_NUMBER, b"S10-123\0\0\0\0\0".to_vec());
+    assert_eq!(item.as_cstring().unwrap(), "S10-123");
+
+    let item = Item::new_tag(crate::tags::INFO::SERIAL_NUMBER, b"S10\0garbage".to_vec());
+    assert_eq!(item.as_cstring().unwrap(), "S10");
+
+    let item = Item::new_tag(crate::tags::INFO::SERIAL_NUMBER, b"S10".to_vec());
+    assert_eq!(item.as_cstring().unwrap(), "S10");
+
+    let item = Item::new_tag(crate::tags::INFO::SERIAL_NUMBER, vec![0x53u8, 0xff, 0x00]);
+    assert_eq!(item.as_cstring().unwrap(), "S\u{fffd}");
+
+    let item = Item::new_tag(crate::tags::INFO::SERIAL_NUMBER, "S10".to_string());
+    assert_eq!(item.as_cstring().unwrap_err().to_string(), "Type mismatch, expected ByteArray got String");
+}
+
+#[test]
+fn test_item_read_bytes_truncated() {
+    let mut buffer_size = 11u16;
+    let mut buffer: std::io::Cursor<Vec<u8>> = std::io::Cursor::new(vec![0x00, 0x00, 0x00, 0x00, 0x0d, 0x04, 0x00, 0x54, 0x65]);
+    let err = Item::read_bytes(&mut buffer, &mut buffer_size).unwrap_err();
+    assert_eq!(format!("{}", err.downcast::<Errors>().unwrap()), "Frame parse error: unexpected end of frame while reading String");
+
+    let mut buffer: std::io::Cursor<Vec<u8>> = std::io::Cursor::new(vec![0x00, 0x00, 0x00, 0x00, 0x0d]);
+    let err = Item::read_bytes(&mut buffer, &mut buffer_size).unwrap_err();
+    assert_eq!(format!("{}", err.downcast::<Errors>().unwrap()), "Frame parse error: unexpected end of frame while reading item header");
+}
+
+#[test]
+fn test_item_write_bytes_short_writes() {
+    let item = Item::new_tag(crate::tags::RSCP::AUTHENTICATION, vec![
+        Item::new_tag(crate::tags::RSCP::AUTHENTICATION_USER, "username".to_string()),
+        Item::new_tag(crate::tags::INFO::TIME, DateTime::<Utc>::from_timestamp(12345678, 123456).unwrap()),
+        Item::new_tag(crate::tags::EMS::POWER_PV, 1200i32),
+    ]);
+    let mut writer = ShortWriter { data: Vec::new() };
+    item.write_bytes(&mut writer).unwrap();
+    assert_eq!(writer.data, item.to_bytes().unwrap());
+}
+
 #[test]
 fn test_get_item_impl() {
-    let item_container = Item::new(crate::tags::RSCP::AUTHENTICATION.into(), vec![
-        Item::new(crate::tags::RSCP::AUTHENTICATION_USER.into(), "username".to_string()),
-        Item::new(crate::tags::RSCP::AUTHENTICATION_PASSWORD.into(), "password".to_string()),
+    let item_container = Item::new_tag(crate::tags::RSCP::AUTHENTICATION, vec![
+        Item::new_tag(crate::tags::RSCP::AUTHENTICATION_USER, "username".to_string()),
+        Item::new_tag(crate::tags::RSCP::AUTHENTICATION_PASSWORD, "password".to_string()),
     ]);
 
     let sub_item = item_container.get_item(crate::tags::RSCP::AUTHENTICATION_USER.into()).unwrap();
@@ -667,6 +1797,17 @@ fn test_get_item_impl() {
     assert_eq!(err_item.unwrap_err().downcast::<String>().unwrap(), "Tag not found 8388607");
 }
 
+#[test]
+fn test_get_item_response_bit() {
+    let item_container = Item::new_tag(crate::tags::RSCP::AUTHENTICATION, vec![
+        Item::new_tag(crate::tags::RSCP::AUTHENTICATION_USER, "username".to_string()),
+        Item { tag: crate::tags::RSCP::AUTHENTICATION_PASSWORD as u32 | 0x00800000, data: Some(Box::new("password".to_string())) },
+    ]);
+    let user_response_tag = crate::tags::RSCP::AUTHENTICATION_USER as u32 | 0x00800000;
+    assert_eq!(item_container.get_item_data::<String>(user_response_tag).unwrap(), "username");
+    assert_eq!(item_container.get_item_data::<String>(crate::tags::RSCP::AUTHENTICATION_PASSWORD.into()).unwrap(), "password");
+}
+
 #[test]
 fn test_display_impl() {
     let test_cases = test_data_cases!();
@@ -676,7 +1817,63 @@ fn test_display_impl() {
     }
 
     let item = Item { tag: 0x00, data: Some(Box::new([1u8, 2, 3, 4, 5])) };
+    assert_eq!(format!("{:?}", item), "Item { tag: \"RSCP_UNKNOWN(0x00000000)\", data: \"None\" }");
+
+    let item = Item::from_tag(crate::tags::RSCP::GENERAL_ERROR);
     assert_eq!(format!("{:?}", item), "Item { tag: \"RSCP_GENERAL_ERROR\", data: \"None\" }");
+
+    let item = Item { tag: 0x07123456, data: None };
+    assert_eq!(format!("{:?}", item), "Item { tag: \"UNKNOWN_UNKNOWN(0x07123456)\", data: \"None\" }");
+}
+
+#[test]
+fn test_debug_redacts_credentials() {
+    let item = Item::new_tag(crate::tags::RSCP::AUTHENTICATION, vec![
+        Item::new_tag(crate::tags::RSCP::AUTHENTICATION_USER, "username".to_string()),
+        Item::new_tag(crate::tags::RSCP::AUTHENTICATION_PASSWORD, "secret_password".to_string()),
+    ]);
+    let debug = format!("{:?}", item);
+    assert!(!debug.contains("username"));
+    assert!(!debug.contains("secret_password"));
+    assert_eq!(debug.matches("\"***\"").count(), 2);
+
+    let item = Item { tag: crate::tags::RSCP::AUTHENTICATION_PASSWORD as u32 | 0x00800000, data: Some(Box::new("secret_password".to_string())) };
+    assert_eq!(format!("{:?}", item), "Item { tag: \"RSCP_AUTHENTICATION_PASSWORD\", data: \"***\" }");
+
+    let item = Item::from_tag(crate::tags::RSCP::AUTHENTICATION_PASSWORD);
+    assert_eq!(format!("{:?}", item), "Item { tag: \"RSCP_AUTHENTICATION_PASSWORD\", data: \"None\" }");
+}
+
+#[test]
+fn test_debug_registered_tag() {
+    crate::tags::register(0x03120001, "BAT_CUSTOM_VOLTAGE");
+    let item = Item { tag: 0x03120001 | 0x00800000, data: Some(Box::new(12.5f32)) };
+    assert_eq!(format!("{:?}", item), "Item { tag: \"BAT_CUSTOM_VOLTAGE\", data: 12.5 }");
+}
+
+#[test]
+fn test_clone_impl() {
+    let test_cases = test_data_cases!();
+    for test_case in test_cases {
+        let item = Item { tag: 0x00, data: test_case.data };
+        assert_eq!(format!("{:?}", item.clone()), test_case.item_str);
+    }
+
+    let item = Item::new_tag(crate::tags::BAT::DATA, vec![
+        Item::new_tag(crate::tags::BAT::INDEX, 0u16),
+        Item::new_tag(crate::tags::BAT::RSOC, ErrorCode::NotAvailable),
+        Item::new_tag(crate::tags::BAT::DCB_INFO, vec![Item { tag: crate::tags::BAT::DCB_INDEX.into(), data: Some(Box::new([1u8, 2])) }]),
+    ]);
+    let clone = item.clone();
+    let container = clone.as_container().unwrap();
+    assert_eq!(container[0].get_data::<u16>().unwrap(), &0);
+    assert_eq!(container[1].get_data::<ErrorCode>().unwrap(), &ErrorCode::NotAvailable);
+    let nested = container[2].as_container().unwrap();
+    assert_eq!(nested[0].tag, crate::tags::BAT::DCB_INDEX as u32);
+    assert!(nested[0].data.is_none());
+
+    let item = Item { tag: 0x00, data: Some(Box::new([1u8, 2, 3, 4, 5])) };
+    assert!(item.clone().data.is_none());
 }
 
 #[test]
@@ -688,11 +1885,67 @@ fn test_get_data_length() {
     }
 }
 
+#[test]
+fn test_get_data_length_oversized() {
+    let item = Item::new_tag(crate::tags::INFO::SERIAL_NUMBER, "x".repeat(70000));
+    assert_eq!(item.to_bytes().unwrap_err().to_string(), "Invalid value: Data length 70000 exceeds maximum of 65535 bytes");
+
+    let item = Item::new_tag(crate::tags::INFO::SERIAL_NUMBER, vec![0u8; 70000]);
+    assert_eq!(item.serialized_len().unwrap_err().to_string(), "Invalid value: Data length 70000 exceeds maximum of 65535 bytes");
+}
+
+#[test]
+fn test_data_length_u16_boundary() {
+    let tag: u32 = crate::tags::INFO::SERIAL_NUMBER.into();
+    let error = "Invalid value: Data length 65536 exceeds maximum of 65535 bytes";
+
+    // bitfield, 8 bits per byte
+    assert_eq!(Item::new(tag, vec![true; 65535 * 8]).to_bytes().unwrap().len(), 65535 + ITEM_HEADER_SIZE as usize);
+    assert_eq!(Item::new(tag, vec![true; 65535 * 8 + 1]).to_bytes().unwrap_err().to_string(), error);
+
+    // string and byte array
+    assert_eq!(Item::new(tag, "x".repeat(65535)).to_bytes().unwrap().len(), 65535 + ITEM_HEADER_SIZE as usize);
+    assert_eq!(Item::new(tag, "x".repeat(65536)).to_bytes().unwrap_err().to_string(), error);
+    assert_eq!(Item::new(tag, vec![0u8; 65535]).to_bytes().unwrap().len(), 65535 + ITEM_HEADER_SIZE as usize);
+    assert_eq!(Item::new(tag, vec![0u8; 65536]).to_bytes().unwrap_err().to_string(), error);
+
+    // container, the sub item headers count towards the length
+    let max_data = 65535 - ITEM_HEADER_SIZE as usize;
+    assert_eq!(Item::new(tag, vec![Item::new(tag, vec![0u8; max_data])]).to_bytes().unwrap().len(), 65535 + ITEM_HEADER_SIZE as usize);
+    assert_eq!(Item::new(tag, vec![Item::new(tag, vec![0u8; max_data + 1])]).to_bytes().unwrap_err().to_string(), error);
+    let items = vec![Item::new(tag, vec![0u8; 40000]), Item::new(tag, vec![0u8; 40000])];
+    assert_eq!(Item::new(tag, items).to_bytes().unwrap_err().to_string(), "Invalid value: Data length 80014 exceeds maximum of 65535 bytes");
+
+    // parsing a bitfield with more than 65535 bits
+    let data = Item::new(tag, vec![true; 10000 * 8]).to_bytes().unwrap();
+    let mut len = data.len() as u16;
+    let item = Item::read_bytes(&mut std::io::Cursor::new(data), &mut len).unwrap();
+    assert_eq!(item.data.get_data::<Vec<bool>>().unwrap().len(), 10000 * 8);
+    assert_eq!(len, 0);
+}
+
+#[test]
+fn test_serialized_len() {
+    let test_cases = test_data_cases!();
+    for test_case in test_cases {
+        let item = Item { tag: 0x00, data: test_case.data };
+        assert_eq!(item.serialized_len().unwrap() as usize, test_case.byte_data.len(), "Test {:?}", test_case.data_type);
+    }
+
+    let item = Item::new_tag(crate::tags::RSCP::AUTHENTICATION, vec![
+        Item::new_tag(crate::tags::RSCP::AUTHENTICATION_USER, "user".to_string()),
+        Item::new_tag(crate::tags::RSCP::AUTHENTICATION_PASSWORD, "pwd".to_string()),
+    ]);
+    let mut buffer: std::io::Cursor<Vec<u8>> = std::io::Cursor::new(Vec::new());
+    item.write_bytes(&mut buffer).unwrap();
+    assert_eq!(item.serialized_len().unwrap() as usize, buffer.get_ref().len());
+}
+
 #[test]
 fn test_get_container_size() {
     let container_size = get_container_size(&vec![
-        Item::new(crate::tags::RSCP::AUTHENTICATION_USER.into(), "user".to_string()),
-        Item::new(crate::tags::RSCP::AUTHENTICATION_PASSWORD.into(), "pwd".to_string()),
+        Item::new_tag(crate::tags::RSCP::AUTHENTICATION_USER, "user".to_string()),
+        Item::new_tag(crate::tags::RSCP::AUTHENTICATION_PASSWORD, "pwd".to_string()),
     ]).unwrap();
     assert_eq!(container_size, 21);
 }
@@ -722,7 +1975,7 @@ fn test_write_data() {
     let none_vec_box: Box<dyn Any> = Box::new(0);
     let mut buffer: std::io::Cursor<Vec<u8>> = std::io::Cursor::new(Vec::new());
     write_data(&mut buffer, &DataType::None, Some(&none_vec_box)).unwrap();
-    assert_eq!(buffer.get_ref().to_vec(), Vec::new(), "Test None {:?}", DataType::None);
+    assert_eq!(buffer.get_ref().to_vec(), Vec::<u8>::new(), "Test None {:?}", DataType::None);
 }
 
 #[test]
@@ -768,3 +2021,94 @@ fn test_read_timestamp() {
     assert_eq!(date_time.timestamp(), 12345678);
     assert_eq!(date_time.timestamp_subsec_nanos(), 123456);
 }
+
+#[test]
+fn test_timestamp_pre_epoch() {
+    let date_times = vec![
+        DateTime::<Utc>::from_timestamp(-1, 500_000_000).unwrap(),
+        DateTime::<Utc>::from_timestamp(-12345678, 123456).unwrap(),
+        DateTime::<Utc>::from_timestamp(-1, 999_999_999).unwrap(),
+        DateTime::<Utc>::from_timestamp(0, 0).unwrap(),
+    ];
+    for date_time in date_times {
+        let mut buffer: std::io::Cursor<Vec<u8>> = std::io::Cursor::new(Vec::new());
+        write_timestamp(&mut buffer, &date_time).unwrap();
+        buffer.set_position(0);
+        assert_eq!(read_timestamp(&mut buffer).unwrap(), date_time, "Test {}", date_time);
+    }
+
+    // 0.5 seconds before epoch written with seconds rounded towards zero
+    let mut buffer: std::io::Cursor<Vec<u8>> = std::io::Cursor::new(vec![0, 0, 0, 0, 0, 0, 0, 0, 0x00, 0x9b, 0x32, 0xe2]);
+    assert_eq!(read_timestamp(&mut buffer).unwrap(), DateTime::<Utc>::from_timestamp(-1, 500_000_000).unwrap());
+
+    let mut buffer: std::io::Cursor<Vec<u8>> = std::io::Cursor::new(vec![0, 0, 0, 0, 0, 0, 0, 0, 0x00, 0xca, 0x9a, 0x3b]);
+    assert_eq!(read_timestamp(&mut buffer).unwrap_err().to_string(), "Frame parse error: Invalid timestamp nanoseconds, got 1000000000");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_to_json() {
+    use serde_json::json;
+
+    let item = Item::new_tag(tags::EMS::GET_SYS_SPECS, vec![
+        Item::new_tag(tags::EMS::POWER_PV, 1200i32),
+        Item::new_tag(tags::EMS::AUTARKY, 0.5f32),
+        Item::new_tag(tags::INFO::SERIAL_NUMBER, "serial".to_string()),
+        Item::new_tag(tags::INFO::MAC_ADDRESS, vec![0x01u8, 0x02u8]),
+        Item::new_tag(tags::EMS::POWERSAVE_ENABLED, vec![true, false]),
+        Item::from_tag(tags::INFO::IP_ADDRESS),
+        Item::new_tag(tags::EMS::POWER_BAT, f64::NAN),
+    ]);
+    assert_eq!(item.to_json(), json!([1200, 0.5, "serial", [1, 2], [true, false], null, null]));
+
+    let item = Item::new_tag(tags::INFO::TIME, DateTime::<Utc>::from_timestamp(1700000000, 500_000_000).unwrap());
+    assert_eq!(item.to_json(), json!("2023-11-14T22:13:20.500+00:00"));
+
+    let item = Item::new_tag(tags::INFO::TIME, ErrorCode::AccessDenied);
+    assert_eq!(item.to_json(), json!("AccessDenied"));
+}
+
+/// writer accepting only a single byte per write call
+#[cfg(test)]
+struct ShortWriter {
+    data: Vec<u8>,
+}
+
+#[cfg(test)]
+impl Write for ShortWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match buf.first() {
+            Some(byte) => {
+                self.data.push(*byte);
+                Ok(1)
+            }
+            None => Ok(0),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_container_builder() {
+    use crate::tags;
+
+    let item = Item::container(tags::EMS::IDLE_PERIOD.into(), |c| {
+        c.item(Item::new_tag(tags::EMS::IDLE_PERIOD_DAY, 2u8));
+        c.container(tags::EMS::IDLE_PERIOD_START.into(), |c| {
+            c.item(Item::new_tag(tags::EMS::IDLE_PERIOD_HOUR, 8u8)).item(Item::new_tag(tags::EMS::IDLE_PERIOD_MINUTE, 30u8));
+        });
+    });
+    assert_eq!(item.to_bytes().unwrap(), vec![
+        0x82, 0x00, 0x00, 0x01, 0x0e, 0x1f, 0x00, // IDLE_PERIOD
+        0x84, 0x00, 0x00, 0x01, 0x03, 0x01, 0x00, 0x02, // IDLE_PERIOD_DAY
+        0x85, 0x00, 0x00, 0x01, 0x0e, 0x10, 0x00, // IDLE_PERIOD_START
+        0x87, 0x00, 0x00, 0x01, 0x03, 0x01, 0x00, 0x08, // IDLE_PERIOD_HOUR
+        0x88, 0x00, 0x00, 0x01, 0x03, 0x01, 0x00, 0x1e, // IDLE_PERIOD_MINUTE
+    ]);
+
+    let empty = Item::container(tags::EMS::IDLE_PERIOD.into(), |_| {});
+    assert!(empty.as_container().unwrap().is_empty());
+}
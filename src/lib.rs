@@ -1,6 +1,20 @@
 #[macro_use]
 extern crate macro_attr;
 
+pub mod bat;
+pub mod device;
+pub mod diag;
+pub mod ems;
+pub mod emshb;
+pub mod ep;
+pub mod gpio;
+pub mod info;
+pub mod mbs;
+pub mod mypv;
+pub mod ovp;
+pub mod pm;
+pub mod se;
+pub mod server;
 pub mod tags;
 
 mod client;
@@ -9,12 +23,25 @@ mod errors;
 mod frame;
 mod getitem;
 mod item;
+#[cfg(feature = "proxy")]
+mod proxy;
 mod read_ext;
+mod transport;
 mod user;
+mod vector;
+mod weekdays;
 
-pub use client::Client;
+pub use client::{Client, ConnectInfo};
+pub use encryption::RscpEncryption;
 pub use errors::{ErrorCode, Errors};
-pub use frame::Frame;
-pub use getitem::GetItem;
-pub use item::Item;
+pub use frame::{hexdump, rscp_crc32, trace_frame, Difference, Frame, FrozenFrame};
+pub use getitem::{GetItem, ItemContainer};
+pub use item::{ContainerBuilder, DataType, Item, DEFAULT_MAX_NESTING_DEPTH};
+pub use read_ext::{Endian, FieldReader, FromBytes};
+pub use transport::{MockClient, Transport};
 pub use user::UserLevel;
+pub use vector::TestVector;
+pub use weekdays::Weekdays;
+
+/// log target of frame and item logging, filter with e.g. `RUST_LOG=rscp=warn`
+pub(crate) const LOG_TARGET: &str = "rscp";
@@ -0,0 +1,171 @@
+use anyhow::Result;
+
+use crate::{tags, Frame, GetItem, Item};
+
+/// Setup value of a modbus connector
+#[derive(Clone, Debug)]
+pub struct ModbusSetup {
+    /// name of the setup value
+    pub name: String,
+
+    /// type of the setup value
+    pub setup_type: u8,
+
+    /// the setup value item, data type depends on the setup type
+    pub value: Option<Item>,
+}
+
+/// Modbus connector of the device
+#[derive(Clone, Debug)]
+pub struct ModbusConnector {
+    /// identifier of the connector
+    pub id: u32,
+
+    /// name of the connector
+    pub name: String,
+
+    /// true if the connector is enabled
+    pub enabled: bool,
+
+    /// setup values of the connector
+    pub setups: Vec<ModbusSetup>,
+}
+
+/// Returns frame requesting the modbus connectors
+pub fn request_connectors() -> Frame {
+    let mut frame = Frame::new();
+    frame.push_item(Item::from_tag(tags::MBS::MODBUS_CONNECTORS));
+    frame
+}
+
+/// Returns frame to enable a modbus connector
+///
+/// # Arguments
+///
+/// * `id` - identifier of the connector
+///
+/// # Examples
+///
+/// ```
+/// let frame = rscp::mbs::enable_connector(1);
+/// ```
+pub fn enable_connector(id: u32) -> Frame {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::MBS::ENABLE_CONNECTOR, id));
+    frame
+}
+
+/// Returns frame to disable a modbus connector
+///
+/// # Arguments
+///
+/// * `id` - identifier of the connector
+pub fn disable_connector(id: u32) -> Frame {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::MBS::DISABLE_CONNECTOR, id));
+    frame
+}
+
+/// Returns frame to enable or disable modbus
+///
+/// # Arguments
+///
+/// * `enabled` - true to enable modbus
+///
+/// # Examples
+///
+/// ```
+/// let frame = rscp::mbs::set_modbus_enabled(true);
+/// ```
+pub fn set_modbus_enabled(enabled: bool) -> Frame {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::MBS::SET_MODBUS_ENABLED, enabled));
+    frame
+}
+
+/// Returns the modbus connectors of a `MODBUS_CONNECTORS` response
+///
+/// # Arguments
+///
+/// * `frame` - the response frame
+pub fn parse_connectors(frame: &Frame) -> Result<Vec<ModbusConnector>> {
+    let mut connectors = Vec::new();
+    for connector in frame.get_item(tags::MBS::MODBUS_CONNECTORS.into())?.as_container()? {
+        if connector.tag != tags::MBS::MODBUS_CONNECTOR_CONTAINER as u32 {
+            continue;
+        }
+        let mut setups = Vec::new();
+        for setup in connector.as_container()? {
+            if setup.tag != tags::MBS::MODBUS_CONNECTOR_SETUP as u32 {
+                continue;
+            }
+            setups.push(ModbusSetup {
+                name: setup.get_item(tags::MBS::MODBUS_SETUP_NAME.into())?.as_string()?.to_string(),
+                setup_type: *setup.get_item_data::<u8>(tags::MBS::MODBUS_SETUP_TYPE.into())?,
+                value: setup.get_item(tags::MBS::MODBUS_SETUP_VALUE.into()).ok().cloned(),
+            });
+        }
+        connectors.push(ModbusConnector {
+            id: connector.get_item(tags::MBS::MODBUS_CONNECTOR_ID.into())?.as_u32()?,
+            name: connector.get_item(tags::MBS::MODBUS_CONNECTOR_NAME.into())?.as_string()?.to_string(),
+            enabled: connector.get_item(tags::MBS::MODBUS_CONNECTOR_ENABLED.into())?.as_bool()?,
+            setups,
+        });
+    }
+    Ok(connectors)
+}
+
+/// ################################################
+///      TEST TEST TEST
+/// ################################################
+
+#[test]
+fn test_command_frames() {
+    let frame = enable_connector(2);
+    assert_eq!(*frame.get_item_data::<u32>(tags::MBS::ENABLE_CONNECTOR.into()).unwrap(), 2);
+
+    let frame = disable_connector(3);
+    assert_eq!(*frame.get_item_data::<u32>(tags::MBS::DISABLE_CONNECTOR.into()).unwrap(), 3);
+
+    let frame = set_modbus_enabled(true);
+    assert!(*frame.get_item_data::<bool>(tags::MBS::SET_MODBUS_ENABLED.into()).unwrap());
+
+    let frame = request_connectors();
+    assert!(frame.get_item(tags::MBS::MODBUS_CONNECTORS.into()).unwrap().data.is_none());
+}
+
+#[test]
+fn test_parse_connectors() {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::MBS::MODBUS_CONNECTORS, vec![
+        Item::new_tag(tags::MBS::MODBUS_CONNECTOR_CONTAINER, vec![
+            Item::new_tag(tags::MBS::MODBUS_CONNECTOR_ID, 1u32),
+            Item::new_tag(tags::MBS::MODBUS_CONNECTOR_NAME, "E3DC".to_string()),
+            Item::new_tag(tags::MBS::MODBUS_CONNECTOR_ENABLED, true),
+            Item::new_tag(tags::MBS::MODBUS_CONNECTOR_SETUP, vec![
+                Item::new_tag(tags::MBS::MODBUS_SETUP_NAME, "Device ID".to_string()),
+                Item::new_tag(tags::MBS::MODBUS_SETUP_TYPE, 1u8),
+                Item::new_tag(tags::MBS::MODBUS_SETUP_VALUE, 1u8),
+            ]),
+        ]),
+        Item::new_tag(tags::MBS::MODBUS_CONNECTOR_CONTAINER, vec![
+            Item::new_tag(tags::MBS::MODBUS_CONNECTOR_ID, 2u32),
+            Item::new_tag(tags::MBS::MODBUS_CONNECTOR_NAME, "SunSpec".to_string()),
+            Item::new_tag(tags::MBS::MODBUS_CONNECTOR_ENABLED, false),
+        ]),
+    ]));
+
+    let connectors = parse_connectors(&frame).unwrap();
+    assert_eq!(connectors.len(), 2);
+    assert_eq!(connectors[0].id, 1);
+    assert_eq!(connectors[0].name, "E3DC");
+    assert!(connectors[0].enabled);
+    assert_eq!(connectors[0].setups.len(), 1);
+    assert_eq!(connectors[0].setups[0].name, "Device ID");
+    assert_eq!(connectors[0].setups[0].setup_type, 1);
+    assert_eq!(*connectors[0].setups[0].value.as_ref().unwrap().get_data::<u8>().unwrap(), 1);
+    assert_eq!(connectors[1].id, 2);
+    assert_eq!(connectors[1].name, "SunSpec");
+    assert!(!connectors[1].enabled);
+    assert!(connectors[1].setups.is_empty());
+}
@@ -0,0 +1,206 @@
+use anyhow::{anyhow, bail, Result};
+
+use crate::{tags, Errors, Frame, GetItem, Item};
+
+/// Boost schedule of a MyPV device, heats up to the temperature in the time span on the week days
+#[derive(Clone, PartialEq, Debug)]
+pub struct BoostSchedule {
+    /// name of the schedule
+    pub name: String,
+
+    /// start time in minutes after midnight
+    pub start: u16,
+
+    /// stop time in minutes after midnight
+    pub stop: u16,
+
+    /// target temperature in degree celsius
+    pub temperature: u16,
+
+    /// true if the schedule is active
+    pub active: bool,
+
+    /// week days of the schedule, index 0 is monday
+    pub weekdays: [bool; 7],
+}
+
+impl BoostSchedule {
+    /// Returns the `DEVICE_BOOST_ITEM` container item
+    pub fn to_item(&self) -> Item {
+        Item::new_tag(tags::MYPV::DEVICE_BOOST_ITEM, vec![
+            Item::new_tag(tags::MYPV::DEVICE_BOOST_NAME, self.name.clone()),
+            Item::new_tag(tags::MYPV::DEVICE_BOOST_START, self.start),
+            Item::new_tag(tags::MYPV::DEVICE_BOOST_STOP, self.stop),
+            Item::new_tag(tags::MYPV::DEVICE_BOOST_TEMPERATURE, self.temperature),
+            Item::new_tag(tags::MYPV::DEVICE_BOOST_ACTIVE, self.active),
+            Item::new_tag(tags::MYPV::DEVICE_BOOST_WEEKDAYS, self.weekdays.to_vec()),
+        ])
+    }
+
+    /// Returns boost schedule parsed from a `DEVICE_BOOST_ITEM` container
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - the boost item container
+    pub fn from_item(item: &Item) -> Result<Self> {
+        item.as_container()?;
+        let weekday_bits = item
+            .get_item(tags::MYPV::DEVICE_BOOST_WEEKDAYS.into())?
+            .data
+            .as_ref()
+            .and_then(|data| data.downcast_ref::<Vec<bool>>())
+            .ok_or_else(|| anyhow!(Errors::Parse("Invalid boost weekdays".to_string())))?;
+        let mut weekdays = [false; 7];
+        for (weekday, bit) in weekdays.iter_mut().zip(weekday_bits) {
+            *weekday = *bit;
+        }
+        Ok(Self {
+            name: item.get_item(tags::MYPV::DEVICE_BOOST_NAME.into())?.as_string()?.to_string(),
+            start: item.get_item(tags::MYPV::DEVICE_BOOST_START.into())?.as_u16()?,
+            stop: item.get_item(tags::MYPV::DEVICE_BOOST_STOP.into())?.as_u16()?,
+            temperature: item.get_item(tags::MYPV::DEVICE_BOOST_TEMPERATURE.into())?.as_u16()?,
+            active: item.get_item(tags::MYPV::DEVICE_BOOST_ACTIVE.into())?.as_bool()?,
+            weekdays,
+        })
+    }
+
+    /// Returns frame writing the boost schedules of the device, replaces the existing schedules
+    ///
+    /// # Arguments
+    ///
+    /// * `serial` - serial number of the device
+    /// * `schedules` - the boost schedules
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::mypv::BoostSchedule;
+    /// let schedule = BoostSchedule {
+    ///     name: "morning".to_string(),
+    ///     start: 6 * 60,
+    ///     stop: 7 * 60,
+    ///     temperature: 55,
+    ///     active: true,
+    ///     weekdays: [true, true, true, true, true, false, false],
+    /// };
+    /// let frame = BoostSchedule::to_write_frame("2001234567", &[schedule]);
+    /// ```
+    pub fn to_write_frame(serial: &str, schedules: &[Self]) -> Frame {
+        let mut frame = Frame::new();
+        frame.push_item(Item::new_tag(tags::MYPV::RSP_WRITE_DEVICES, vec![Item::new_tag(tags::MYPV::DEVICE, vec![
+            Item::new_tag(tags::MYPV::DEVICE_SERIAL, serial.to_string()),
+            Item::new_tag(tags::MYPV::DEVICE_BOOST_LIST, schedules.iter().map(Self::to_item).collect::<Vec<Item>>()),
+        ])]));
+        frame
+    }
+
+    /// Returns the boost schedules of the device in the `RSP_LIST_DEVICES` container of the frame
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - the response frame
+    /// * `serial` - serial number of the device
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rscp::mypv::{self, BoostSchedule};
+    /// let mut c = rscp::Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    /// c.connect("energy.storage.local", None).unwrap();
+    /// let result_frame = c.send_receive_frame(&mypv::list_devices()).unwrap();
+    /// for schedule in BoostSchedule::from_frame(&result_frame, "2001234567").unwrap() {
+    ///     println!("{}: {} - {}", schedule.name, schedule.start, schedule.stop);
+    /// }
+    /// ```
+    pub fn from_frame(frame: &Frame, serial: &str) -> Result<Vec<Self>> {
+        for device in frame.get_item(tags::MYPV::RSP_LIST_DEVICES.into())?.as_container()? {
+            if device.tag == tags::MYPV::DEVICE as u32 && device.get_item(tags::MYPV::DEVICE_SERIAL.into())?.as_string()? == serial {
+                let mut schedules = Vec::new();
+                for item in device.get_item(tags::MYPV::DEVICE_BOOST_LIST.into())?.as_container()? {
+                    if item.tag == tags::MYPV::DEVICE_BOOST_ITEM as u32 {
+                        schedules.push(Self::from_item(item)?);
+                    }
+                }
+                return Ok(schedules);
+            }
+        }
+        bail!(Errors::Parse(format!("Missing MyPV device {}", serial)))
+    }
+}
+
+/// Returns frame to search for MyPV devices in the network
+pub fn find_devices() -> Frame {
+    let mut frame = Frame::new();
+    frame.push_item(Item::from_tag(tags::MYPV::RSP_FIND_DEVICES));
+    frame
+}
+
+/// Returns frame requesting the known MyPV devices
+pub fn list_devices() -> Frame {
+    let mut frame = Frame::new();
+    frame.push_item(Item::from_tag(tags::MYPV::RSP_LIST_DEVICES));
+    frame
+}
+
+/// Returns frame to start the instant boost of the device
+///
+/// # Arguments
+///
+/// * `serial` - serial number of the device
+pub fn instant_boost(serial: &str) -> Frame {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::MYPV::RSP_INSTANT_BOOST, vec![Item::new_tag(tags::MYPV::DEVICE, vec![
+        Item::new_tag(tags::MYPV::DEVICE_SERIAL, serial.to_string()),
+    ])]));
+    frame
+}
+
+/// ################################################
+///      TEST TEST TEST
+/// ################################################
+
+#[test]
+fn test_boost_schedule_round_trip() {
+    let schedules = vec![
+        BoostSchedule {
+            name: "morning".to_string(),
+            start: 6 * 60,
+            stop: 7 * 60 + 30,
+            temperature: 55,
+            active: true,
+            weekdays: [true, true, true, true, true, false, false],
+        },
+        BoostSchedule {
+            name: "weekend".to_string(),
+            start: 10 * 60,
+            stop: 12 * 60,
+            temperature: 60,
+            active: false,
+            weekdays: [false, false, false, false, false, true, true],
+        },
+    ];
+
+    // the device lists the written schedules, round trip through the wire format
+    let write_frame = BoostSchedule::to_write_frame("2001234567", &schedules);
+    let devices = write_frame.get_item(tags::MYPV::RSP_WRITE_DEVICES.into()).unwrap().as_container().unwrap().to_vec();
+    let mut list_frame = Frame::new();
+    list_frame.push_item(Item::new_tag(tags::MYPV::RSP_LIST_DEVICES, devices));
+    let list_frame = Frame::from_bytes(list_frame.to_bytes().unwrap()).unwrap();
+
+    assert_eq!(BoostSchedule::from_frame(&list_frame, "2001234567").unwrap(), schedules);
+    assert_eq!(format!("{}", BoostSchedule::from_frame(&list_frame, "2009999999").unwrap_err()), "Frame parse error: Missing MyPV device 2009999999");
+    assert!(BoostSchedule::from_frame(&list_devices(), "2001234567").is_err());
+
+    let mut item = schedules[0].to_item();
+    item.data.as_mut().unwrap().downcast_mut::<Vec<Item>>().unwrap()[1] = Item::new_tag(tags::MYPV::DEVICE_BOOST_START, 360u32);
+    assert_eq!(format!("{}", BoostSchedule::from_item(&item).unwrap_err()), "Type mismatch, expected UInt16 got UInt32");
+}
+
+#[test]
+fn test_instant_boost() {
+    let frame = instant_boost("2001234567");
+    let device = frame.get_item(tags::MYPV::RSP_INSTANT_BOOST.into()).unwrap().get_item(tags::MYPV::DEVICE.into()).unwrap();
+    assert_eq!(device.get_item_data::<String>(tags::MYPV::DEVICE_SERIAL.into()).unwrap(), "2001234567");
+
+    assert!(find_devices().get_item(tags::MYPV::RSP_FIND_DEVICES.into()).unwrap().data.is_none());
+}
@@ -0,0 +1,130 @@
+use anyhow::Result;
+
+use crate::{tags, Frame, GetItem, Item};
+
+/// Status of the overvoltage protection
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct OvpStatus {
+    /// index of the overvoltage protection
+    pub index: Option<u8>,
+
+    /// status code reported by the device
+    pub status: u8,
+}
+
+/// Returns frame requesting the overvoltage protection status
+///
+/// # Arguments
+///
+/// * `index` - index of the overvoltage protection
+pub fn status_frame(index: u8) -> Frame {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::OVP::STATUS, vec![Item::new_tag(tags::OVP::PARAM_INDEX, index)]));
+    frame
+}
+
+/// Returns frame to reset the overvoltage protection after a fault
+///
+/// # Arguments
+///
+/// * `index` - index of the overvoltage protection
+///
+/// # Examples
+///
+/// ```
+/// let frame = rscp::ovp::reset_frame(0);
+/// ```
+pub fn reset_frame(index: u8) -> Frame {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::OVP::RESET, vec![
+        Item::new_tag(tags::OVP::PARAM_INDEX, index),
+        Item::new_tag(tags::OVP::PARAM_RESET, true),
+    ]));
+    frame
+}
+
+/// Returns frame requesting if the device supports overvoltage protection
+pub fn possible_frame() -> Frame {
+    let mut frame = Frame::new();
+    frame.push_item(Item::from_tag(tags::INFO::IS_OVP_POSSIBLE));
+    frame
+}
+
+/// Returns the status of the `STATUS` container in the frame
+///
+/// # Arguments
+///
+/// * `frame` - the response frame
+///
+/// # Examples
+///
+/// ```no_run
+/// use rscp::ovp;
+/// let mut c = rscp::Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+/// c.connect("energy.storage.local", None).unwrap();
+/// let result_frame = c.send_receive_frame(&ovp::status_frame(0)).unwrap();
+/// println!("{:?}", ovp::parse_status(&result_frame).unwrap());
+/// ```
+pub fn parse_status(frame: &Frame) -> Result<OvpStatus> {
+    let container = frame.get_item(tags::OVP::STATUS.into())?;
+    // the device responds with an error code instead of the container e.g. if access is denied
+    container.as_container()?;
+    Ok(OvpStatus {
+        index: match container.get_item(tags::OVP::PARAM_INDEX.into()) {
+            Ok(item) => Some(item.as_u8()?),
+            Err(_) => None,
+        },
+        status: container.get_item(tags::OVP::PARAM_STATUS.into())?.as_u8()?,
+    })
+}
+
+/// Returns true if the `IS_OVP_POSSIBLE` item in the frame reports overvoltage protection support
+///
+/// # Arguments
+///
+/// * `frame` - the response frame
+pub fn parse_possible(frame: &Frame) -> Result<bool> {
+    frame.get_item(tags::INFO::IS_OVP_POSSIBLE.into())?.as_bool()
+}
+
+/// ################################################
+///      TEST TEST TEST
+/// ################################################
+
+#[test]
+fn test_frames() {
+    let frame = status_frame(1);
+    let container = frame.get_item(tags::OVP::STATUS.into()).unwrap();
+    assert_eq!(container.get_item_data::<u8>(tags::OVP::PARAM_INDEX.into()).unwrap(), &1);
+
+    let frame = reset_frame(0);
+    let container = frame.get_item(tags::OVP::RESET.into()).unwrap();
+    assert_eq!(container.get_item_data::<u8>(tags::OVP::PARAM_INDEX.into()).unwrap(), &0);
+    assert_eq!(container.get_item_data::<bool>(tags::OVP::PARAM_RESET.into()).unwrap(), &true);
+
+    assert!(possible_frame().get_item(tags::INFO::IS_OVP_POSSIBLE.into()).unwrap().data.is_none());
+}
+
+#[test]
+fn test_parse_status() {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::OVP::STATUS, vec![
+        Item::new_tag(tags::OVP::PARAM_INDEX, 0u8),
+        Item::new_tag(tags::OVP::PARAM_STATUS, 2u8),
+    ]));
+    assert_eq!(parse_status(&frame).unwrap(), OvpStatus { index: Some(0), status: 2 });
+
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::OVP::STATUS, vec![Item::new_tag(tags::OVP::PARAM_STATUS, 0u8)]));
+    assert_eq!(parse_status(&frame).unwrap(), OvpStatus { index: None, status: 0 });
+
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::OVP::STATUS, vec![Item::new_tag(tags::OVP::PARAM_STATUS, "0".to_string())]));
+    assert_eq!(parse_status(&frame).unwrap_err().to_string(), "Type mismatch, expected UChar8 got String");
+
+    assert!(parse_status(&status_frame(0)).is_err());
+
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::INFO::IS_OVP_POSSIBLE, true));
+    assert!(parse_possible(&frame).unwrap());
+}
@@ -0,0 +1,125 @@
+use anyhow::Result;
+
+use crate::{tags, Frame, GetItem, Item};
+
+/// Readings of a power meter
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct PowerMeter {
+    /// index of the power meter
+    pub index: u8,
+
+    /// power per phase in watt
+    pub power: [f64; 3],
+
+    /// energy per phase in watt hours
+    pub energy: [f64; 3],
+
+    /// voltage per phase in volt
+    pub voltage: [f32; 3],
+
+    /// bitmask of the active phases
+    pub active_phases: u8,
+
+    /// mode of the power meter
+    pub mode: u8,
+}
+
+impl PowerMeter {
+    /// Returns power meter parsed from the `DATA` container of the frame
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - the response frame
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rscp::pm::{self, PowerMeter};
+    /// let mut c = rscp::Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    /// c.connect("energy.storage.local", None).unwrap();
+    /// let result_frame = c.send_receive_frame(&pm::request_power_meter(0)).unwrap();
+    /// let power_meter = PowerMeter::from_frame(&result_frame).unwrap();
+    /// println!("{:?} W", power_meter.power);
+    /// ```
+    pub fn from_frame(frame: &Frame) -> Result<Self> {
+        let container = frame.get_item(tags::PM::DATA.into())?;
+        container.as_container()?;
+        let f64_item = |tag: tags::PM| -> Result<f64> { container.get_item(tag.into())?.as_f64() };
+        let f32_item = |tag: tags::PM| -> Result<f32> { container.get_item(tag.into())?.as_f32() };
+        Ok(Self {
+            index: container.get_item(tags::PM::INDEX.into())?.as_u8()?,
+            power: [f64_item(tags::PM::POWER_L1)?, f64_item(tags::PM::POWER_L2)?, f64_item(tags::PM::POWER_L3)?],
+            energy: [f64_item(tags::PM::ENERGY_L1)?, f64_item(tags::PM::ENERGY_L2)?, f64_item(tags::PM::ENERGY_L3)?],
+            voltage: [f32_item(tags::PM::VOLTAGE_L1)?, f32_item(tags::PM::VOLTAGE_L2)?, f32_item(tags::PM::VOLTAGE_L3)?],
+            active_phases: container.get_item(tags::PM::ACTIVE_PHASES.into())?.as_u8()?,
+            mode: container.get_item(tags::PM::MODE.into())?.as_u8()?,
+        })
+    }
+}
+
+/// Returns frame requesting the readings of the power meter
+///
+/// # Arguments
+///
+/// * `index` - index of the power meter
+pub fn request_power_meter(index: u8) -> Frame {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::PM::DATA, vec![
+        Item::new_tag(tags::PM::INDEX, index),
+        Item::from_tag(tags::PM::POWER_L1),
+        Item::from_tag(tags::PM::POWER_L2),
+        Item::from_tag(tags::PM::POWER_L3),
+        Item::from_tag(tags::PM::ENERGY_L1),
+        Item::from_tag(tags::PM::ENERGY_L2),
+        Item::from_tag(tags::PM::ENERGY_L3),
+        Item::from_tag(tags::PM::VOLTAGE_L1),
+        Item::from_tag(tags::PM::VOLTAGE_L2),
+        Item::from_tag(tags::PM::VOLTAGE_L3),
+        Item::from_tag(tags::PM::ACTIVE_PHASES),
+        Item::from_tag(tags::PM::MODE),
+    ]));
+    frame
+}
+
+/// ################################################
+///      TEST TEST TEST
+/// ################################################
+
+#[test]
+fn test_request_power_meter() {
+    let frame = request_power_meter(1);
+    let data = frame.get_item(tags::PM::DATA.into()).unwrap();
+    assert_eq!(data.get_item_data::<u8>(tags::PM::INDEX.into()).unwrap(), &1);
+    assert_eq!(data.as_container().unwrap().len(), 12);
+}
+
+#[test]
+fn test_power_meter_from_frame() {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::PM::DATA, vec![
+        Item::new_tag(tags::PM::INDEX, 0u8),
+        Item::new_tag(tags::PM::POWER_L1, 1200.0f64),
+        Item::new_tag(tags::PM::POWER_L2, -300.0f64),
+        Item::new_tag(tags::PM::POWER_L3, 50.5f64),
+        Item::new_tag(tags::PM::ENERGY_L1, 10000.0f64),
+        Item::new_tag(tags::PM::ENERGY_L2, 20000.0f64),
+        Item::new_tag(tags::PM::ENERGY_L3, 30000.0f64),
+        Item::new_tag(tags::PM::VOLTAGE_L1, 230.5f32),
+        Item::new_tag(tags::PM::VOLTAGE_L2, 231.0f32),
+        Item::new_tag(tags::PM::VOLTAGE_L3, 229.5f32),
+        Item::new_tag(tags::PM::ACTIVE_PHASES, 0b111u8),
+        Item::new_tag(tags::PM::MODE, 1u8),
+    ]));
+    let frame = Frame::from_bytes(frame.to_bytes().unwrap()).unwrap();
+
+    assert_eq!(PowerMeter::from_frame(&frame).unwrap(), PowerMeter {
+        index: 0,
+        power: [1200.0, -300.0, 50.5],
+        energy: [10000.0, 20000.0, 30000.0],
+        voltage: [230.5, 231.0, 229.5],
+        active_phases: 0b111,
+        mode: 1,
+    });
+
+    assert!(PowerMeter::from_frame(&request_power_meter(0)).is_err());
+}
@@ -0,0 +1,199 @@
+use anyhow::{bail, Result};
+use std::io::{Read, Write};
+use std::net::{SocketAddr, TcpStream};
+use std::time::Duration;
+
+use crate::Errors;
+
+/// SOCKS protocol version
+const SOCKS_VERSION: u8 = 0x05;
+
+/// authentication method without authentication
+const NO_AUTHENTICATION: u8 = 0x00;
+
+/// CONNECT command
+const CMD_CONNECT: u8 = 0x01;
+
+/// address types of requests and replies
+const ATYP_IPV4: u8 = 0x01;
+const ATYP_DOMAIN: u8 = 0x03;
+const ATYP_IPV6: u8 = 0x04;
+
+/// successful reply
+const REPLY_SUCCEEDED: u8 = 0x00;
+
+/// timeout of connecting to the proxy and of each read and write of the handshake
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Opens a connection to the host through the SOCKS5 proxy, the host name is resolved by the proxy
+///
+/// # Arguments
+///
+/// * `proxy` - address of the SOCKS5 proxy
+/// * `host` - target host name or address
+/// * `port` - target port
+pub(crate) fn connect(proxy: SocketAddr, host: &str, port: u16) -> Result<TcpStream> {
+    connect_with_timeout(proxy, host, port, HANDSHAKE_TIMEOUT)
+}
+
+/// Opens a connection to the host through the SOCKS5 proxy, a proxy not answering within the
+/// timeout is an error, the returned stream has no timeouts set
+///
+/// # Arguments
+///
+/// * `proxy` - address of the SOCKS5 proxy
+/// * `host` - target host name or address
+/// * `port` - target port
+/// * `timeout` - timeout of connecting and of each read and write of the handshake
+fn connect_with_timeout(proxy: SocketAddr, host: &str, port: u16, timeout: Duration) -> Result<TcpStream> {
+    let mut stream = TcpStream::connect_timeout(&proxy, timeout)?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+    handshake(&mut stream, host, port)?;
+    stream.set_read_timeout(None)?;
+    stream.set_write_timeout(None)?;
+    Ok(stream)
+}
+
+/// Negotiates the connection to the host on a stream connected to the proxy
+///
+/// # Arguments
+///
+/// * `stream` - stream connected to the proxy
+/// * `host` - target host name or address
+/// * `port` - target port
+fn handshake<S: Read + Write>(stream: &mut S, host: &str, port: u16) -> Result<()> {
+    if host.is_empty() || host.len() > u8::MAX as usize {
+        bail!(Errors::Proxy(format!("Invalid host name length {}", host.len())))
+    }
+
+    // greeting with supported authentication methods
+    stream.write_all(&[SOCKS_VERSION, 0x01, NO_AUTHENTICATION])?;
+    let mut reply = [0u8; 2];
+    stream.read_exact(&mut reply)?;
+    if reply != [SOCKS_VERSION, NO_AUTHENTICATION] {
+        bail!(Errors::Proxy(format!("Authentication method not accepted, got {:02x?}", reply)))
+    }
+
+    // connect request with domain name
+    let mut request = vec![SOCKS_VERSION, CMD_CONNECT, 0x00, ATYP_DOMAIN, host.len() as u8];
+    request.extend_from_slice(host.as_bytes());
+    request.extend_from_slice(&port.to_be_bytes());
+    stream.write_all(&request)?;
+
+    let mut reply = [0u8; 4];
+    stream.read_exact(&mut reply)?;
+    if reply[0] != SOCKS_VERSION || reply[1] != REPLY_SUCCEEDED {
+        bail!(Errors::Proxy(format!("Connect to {}:{} failed with reply {:#04x}", host, port, reply[1])))
+    }
+
+    // skip bound address and port
+    let address_len = match reply[3] {
+        ATYP_IPV4 => 4,
+        ATYP_IPV6 => 16,
+        ATYP_DOMAIN => {
+            let mut len = [0u8; 1];
+            stream.read_exact(&mut len)?;
+            len[0] as usize
+        }
+        atyp => bail!(Errors::Proxy(format!("Invalid address type {:#04x}", atyp))),
+    };
+    let mut bound = vec![0u8; address_len + 2];
+    stream.read_exact(&mut bound)?;
+    Ok(())
+}
+
+/// ################################################
+///      TEST TEST TEST
+/// ################################################
+
+#[test]
+fn test_connect() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let proxy = listener.local_addr().unwrap();
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut greeting = [0u8; 3];
+        stream.read_exact(&mut greeting).unwrap();
+        assert_eq!(greeting, [0x05, 0x01, 0x00]);
+        stream.write_all(&[0x05, 0x00]).unwrap();
+
+        let mut request = [0u8; 5 + 20 + 2];
+        stream.read_exact(&mut request).unwrap();
+        assert_eq!(&request[..5], &[0x05, 0x01, 0x00, 0x03, 20]);
+        assert_eq!(&request[5..25], b"energy.storage.local");
+        assert_eq!(&request[25..], &5033u16.to_be_bytes());
+        stream.write_all(&[0x05, 0x00, 0x00, 0x01, 127, 0, 0, 1, 0x13, 0xa9]).unwrap();
+
+        // relayed data of the target
+        stream.write_all(b"rscp").unwrap();
+    });
+
+    let mut stream = connect(proxy, "energy.storage.local", 5033).unwrap();
+    let mut data = [0u8; 4];
+    stream.read_exact(&mut data).unwrap();
+    assert_eq!(&data, b"rscp");
+}
+
+#[test]
+fn test_connect_timeout() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let proxy = listener.local_addr().unwrap();
+    let server = std::thread::spawn(move || {
+        // accept the connection without answering the greeting
+        let (stream, _) = listener.accept().unwrap();
+        std::thread::sleep(Duration::from_millis(500));
+        drop(stream);
+    });
+
+    let start = std::time::Instant::now();
+    assert!(connect_with_timeout(proxy, "energy.storage.local", 5033, Duration::from_millis(100)).is_err());
+    assert!(start.elapsed() < Duration::from_millis(400));
+    server.join().unwrap();
+}
+
+#[test]
+fn test_handshake_errors() {
+    let mut stream = TestStream::new(vec![0x05, 0xff]);
+    assert_eq!(handshake(&mut stream, "host", 5033).unwrap_err().to_string(), "Proxy error: Authentication method not accepted, got [05, ff]");
+
+    let mut stream = TestStream::new(vec![0x05, 0x00, 0x05, 0x05, 0x00, 0x01]);
+    assert_eq!(handshake(&mut stream, "host", 5033).unwrap_err().to_string(), "Proxy error: Connect to host:5033 failed with reply 0x05");
+
+    let mut stream = TestStream::new(vec![0x05, 0x00, 0x05, 0x00, 0x00, 0x03, 0x04, b'h', b'o', b's', b't', 0x13, 0xa9]);
+    assert!(handshake(&mut stream, "host", 5033).is_ok());
+
+    let mut stream = TestStream::new(Vec::new());
+    assert_eq!(handshake(&mut stream, "", 5033).unwrap_err().to_string(), "Proxy error: Invalid host name length 0");
+}
+
+/// stream returning the given data on reads and discarding writes
+#[cfg(test)]
+struct TestStream {
+    input: std::io::Cursor<Vec<u8>>,
+}
+
+#[cfg(test)]
+impl TestStream {
+    fn new(data: Vec<u8>) -> Self {
+        Self { input: std::io::Cursor::new(data) }
+    }
+}
+
+#[cfg(test)]
+impl Read for TestStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.input.read(buf)
+    }
+}
+
+#[cfg(test)]
+impl Write for TestStream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
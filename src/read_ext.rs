@@ -38,3 +38,67 @@ macro_rules! impl_from_bytes {
 impl_from_bytes! { u8 i8 u16 i16 u32 i32 u64 i64 f32 f64 }
 
 impl<R, const N: usize> ReadExt<N> for R where R: std::io::Read {}
+
+/// Byte order of a field.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Endian {
+    Little,
+    Big,
+}
+
+/// Reader for payloads with mixed byte order, the byte order is given per field.
+pub struct FieldReader<R: std::io::Read> {
+    reader: R,
+}
+
+impl<R: std::io::Read> FieldReader<R> {
+    /// Returns field reader
+    ///
+    /// # Arguments
+    ///
+    /// * `reader` - the reader
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::io::Cursor;
+    /// use rscp::{Endian, FieldReader};
+    /// let mut reader = FieldReader::new(Cursor::new(vec![0xe3, 0xdc, 0x01, 0x00]));
+    /// assert_eq!(reader.read::<u16, 2>(Endian::Big).unwrap(), 0xe3dc);
+    /// assert_eq!(reader.read::<u16, 2>(Endian::Little).unwrap(), 0x0001);
+    /// ```
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+
+    /// Reads a field in the given byte order
+    ///
+    /// # Arguments
+    ///
+    /// * `endian` - byte order of the field
+    pub fn read<T: FromBytes<N>, const N: usize>(&mut self, endian: Endian) -> std::io::Result<T> {
+        match endian {
+            Endian::Little => ReadExt::<N>::read_le(&mut self.reader),
+            Endian::Big => ReadExt::<N>::read_be(&mut self.reader),
+        }
+    }
+
+    /// Returns the inner reader
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+}
+
+/// ################################################
+///      TEST TEST TEST
+/// ################################################
+
+#[test]
+fn test_field_reader() {
+    let mut reader = FieldReader::new(std::io::Cursor::new(vec![0x12, 0x34, 0x56, 0x78, 0x12, 0x34, 0x56, 0x78, 0xff]));
+    assert_eq!(reader.read::<u32, 4>(Endian::Big).unwrap(), 0x12345678);
+    assert_eq!(reader.read::<u32, 4>(Endian::Little).unwrap(), 0x78563412);
+    assert_eq!(reader.read::<i8, 1>(Endian::Big).unwrap(), -1);
+    assert!(reader.read::<u8, 1>(Endian::Big).is_err());
+    assert_eq!(reader.into_inner().position(), 9);
+}
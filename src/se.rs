@@ -0,0 +1,108 @@
+use anyhow::Result;
+
+use crate::{tags, Frame, GetItem, Item};
+
+/// Emergency power reserve of the storage
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct EpReserve {
+    /// reserve in percent of the battery capacity
+    pub percent: f32,
+
+    /// reserve in watt hours
+    pub watt_hours: f32,
+
+    /// maximum possible reserve in watt hours
+    pub max_watt_hours: Option<f32>,
+}
+
+impl EpReserve {
+    /// Returns reserve parsed from the `EP_RESERVE` container of the frame
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - the response frame
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rscp::se::{self, EpReserve};
+    /// let mut c = rscp::Client::new("RSCP_KEY", "RSCP_USER".to_string(), "RSCP_PASSWORD".to_string());
+    /// c.connect("energy.storage.local", None).unwrap();
+    /// let reserve = EpReserve::from_frame(&c.send_receive_frame(&se::request_ep_reserve()).unwrap()).unwrap();
+    /// println!("{} % / {} Wh", reserve.percent, reserve.watt_hours);
+    /// ```
+    pub fn from_frame(frame: &Frame) -> Result<Self> {
+        let container = frame.get_item(tags::SE::EP_RESERVE.into())?;
+        // the device responds with an error code instead of the container e.g. if access is denied
+        container.as_container()?;
+        Ok(Self {
+            percent: container.get_item(tags::SE::PARAM_EP_RESERVE.into())?.as_f32()?,
+            watt_hours: container.get_item(tags::SE::PARAM_EP_RESERVE_W.into())?.as_f32()?,
+            max_watt_hours: match container.get_item(tags::SE::PARAM_EP_RESERVE_MAX_W.into()) {
+                Ok(item) => Some(item.as_f32()?),
+                Err(_) => None,
+            },
+        })
+    }
+}
+
+/// Returns frame requesting the emergency power reserve
+pub fn request_ep_reserve() -> Frame {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::SE::EP_RESERVE, vec![Item::new_tag(tags::SE::PARAM_INDEX, 0u8)]));
+    frame
+}
+
+/// Returns frame to set the emergency power reserve
+///
+/// # Arguments
+///
+/// * `percent` - reserve in percent of the battery capacity
+///
+/// # Examples
+///
+/// ```
+/// let frame = rscp::se::set_ep_reserve(20.0);
+/// ```
+pub fn set_ep_reserve(percent: f32) -> Frame {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::SE::SET_EP_RESERVE, vec![
+        Item::new_tag(tags::SE::PARAM_INDEX, 0u8),
+        Item::new_tag(tags::SE::PARAM_EP_RESERVE, percent),
+    ]));
+    frame
+}
+
+/// ################################################
+///      TEST TEST TEST
+/// ################################################
+
+#[test]
+fn test_set_ep_reserve() {
+    let data = set_ep_reserve(25.5).to_bytes().unwrap();
+    let frame = Frame::from_bytes(data).unwrap();
+    let container = frame.get_item(tags::SE::SET_EP_RESERVE.into()).unwrap();
+    assert_eq!(container.get_item_data::<u8>(tags::SE::PARAM_INDEX.into()).unwrap(), &0);
+    assert_eq!(container.get_item(tags::SE::PARAM_EP_RESERVE.into()).unwrap().as_f32().unwrap(), 25.5);
+}
+
+#[test]
+fn test_ep_reserve_from_frame() {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::SE::EP_RESERVE, vec![
+        Item::new_tag(tags::SE::PARAM_INDEX, 0u8),
+        Item::new_tag(tags::SE::PARAM_EP_RESERVE, 20.0f32),
+        Item::new_tag(tags::SE::PARAM_EP_RESERVE_W, 2760.0f32),
+        Item::new_tag(tags::SE::PARAM_EP_RESERVE_MAX_W, 13800.0f32),
+    ]));
+    assert_eq!(EpReserve::from_frame(&frame).unwrap(), EpReserve { percent: 20.0, watt_hours: 2760.0, max_watt_hours: Some(13800.0) });
+
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::SE::EP_RESERVE, vec![
+        Item::new_tag(tags::SE::PARAM_EP_RESERVE, 10.0f32),
+        Item::new_tag(tags::SE::PARAM_EP_RESERVE_W, 1380.0f32),
+    ]));
+    assert_eq!(EpReserve::from_frame(&frame).unwrap(), EpReserve { percent: 10.0, watt_hours: 1380.0, max_watt_hours: None });
+
+    assert!(EpReserve::from_frame(&request_ep_reserve()).is_err());
+}
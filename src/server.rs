@@ -0,0 +1,73 @@
+use anyhow::{bail, Result};
+
+use crate::{tags, Errors, Frame, GetItem, Item};
+
+/// Returns `RSCP_CMD` container wrapping the frame, used to talk to a device through the server relay
+///
+/// # Arguments
+///
+/// * `frame` - the inner frame
+///
+/// # Examples
+///
+/// ```
+/// use rscp::{server, tags, Frame, Item};
+/// let mut info_frame = Frame::new();
+/// info_frame.push_item(Item { tag: tags::INFO::SERIAL_NUMBER.into(), data: None });
+/// let mut frame = Frame::new();
+/// frame.push_item(server::wrap_frame(&info_frame).unwrap());
+/// ```
+pub fn wrap_frame(frame: &Frame) -> Result<Item> {
+    let data = frame.to_bytes()?;
+    Ok(Item::new_tag(tags::SERVER::RSCP_CMD, vec![
+        Item::new_tag(tags::SERVER::RSCP_DATA_LEN, data.len() as u32),
+        Item::new_tag(tags::SERVER::RSCP_DATA, data),
+    ]))
+}
+
+/// Returns the inner frame of a `RSCP_CMD_RESP` container
+///
+/// # Arguments
+///
+/// * `item` - the response container item
+///
+/// # Examples
+///
+/// ```no_run
+/// use rscp::{server, tags, GetItem};
+/// # let result_frame = rscp::Frame::new();
+/// let inner_frame = server::unwrap_frame(result_frame.get_item(tags::SERVER::RSCP_CMD_RESP.into()).unwrap()).unwrap();
+/// ```
+pub fn unwrap_frame(item: &Item) -> Result<Frame> {
+    let data_len = item.get_item(tags::SERVER::RSCP_DATA_LEN.into())?.as_u32()?;
+    let data = item.get_item(tags::SERVER::RSCP_DATA.into())?.as_bytes()?;
+    if data_len as usize != data.len() {
+        bail!(Errors::Parse(format!("RSCP data length missmatch, got {} expected {}", data.len(), data_len)))
+    }
+    Frame::from_bytes(data.to_vec())
+}
+
+/// ################################################
+///      TEST TEST TEST
+/// ################################################
+
+#[test]
+fn test_wrap_unwrap_frame() {
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::INFO::SERIAL_NUMBER, "serial".to_string()));
+
+    let item = wrap_frame(&frame).unwrap();
+    assert_eq!(item.tag, tags::SERVER::RSCP_CMD as u32);
+    assert_eq!(item.get_item(tags::SERVER::RSCP_DATA_LEN.into()).unwrap().as_u32().unwrap() as usize, frame.to_bytes().unwrap().len());
+
+    let response = Item::new_tag(tags::SERVER::RSCP_CMD_RESP, item.as_container().unwrap().to_vec());
+    let inner_frame = unwrap_frame(&response).unwrap();
+    assert_eq!(inner_frame.get_item_data::<String>(tags::INFO::SERIAL_NUMBER.into()).unwrap(), "serial");
+    assert_eq!(inner_frame.to_bytes().unwrap(), frame.to_bytes().unwrap());
+
+    let response = Item::new_tag(tags::SERVER::RSCP_CMD_RESP, vec![
+        Item::new_tag(tags::SERVER::RSCP_DATA_LEN, 1u32),
+        Item::new_tag(tags::SERVER::RSCP_DATA, frame.to_bytes().unwrap()),
+    ]);
+    assert!(unwrap_frame(&response).is_err());
+}
@@ -1,4 +1,133 @@
+use std::collections::HashMap;
 use std::fmt::{Display, Formatter, Result};
+use std::sync::{OnceLock, RwLock};
+
+use crate::item::TAG_MASK;
+
+/// Returns the tag with the response bit cleared
+///
+/// # Arguments
+///
+/// * `tag` - the request or response tag
+///
+/// # Examples
+///
+/// ```
+/// use rscp::tags::{as_request, INFO};
+/// assert_eq!(as_request(INFO::SERIAL_NUMBER as u32 | 0x00800000), INFO::SERIAL_NUMBER as u32);
+/// ```
+pub fn as_request(tag: u32) -> u32 {
+    tag & TAG_MASK
+}
+
+/// names of the bits of `EMS::STATUS`
+const EMS_STATUS_BITS: [&str; 7] = [
+    "CHARGE_LOCKED",
+    "DISCHARGE_LOCKED",
+    "EMERGENCY_POWER_POSSIBLE",
+    "WEATHER_REGULATED_CHARGE_ACTIVE",
+    "POWER_LIMIT_ACTIVE",
+    "CHARGE_LOCK_TIME_ACTIVE",
+    "DISCHARGE_LOCK_TIME_ACTIVE",
+];
+
+/// Returns the named bits of a status bitfield, bits without name are skipped
+///
+/// Bit names are known for `EMS::STATUS`, other tags return an empty list.
+///
+/// # Arguments
+///
+/// * `tag` - the tag of the bitfield
+/// * `bits` - the bits, index 0 is the lowest bit
+///
+/// # Examples
+///
+/// ```
+/// use rscp::tags::{named_bits, EMS};
+/// for (name, value) in named_bits(EMS::STATUS.into(), &[true, false, true]) {
+///     println!("{}: {}", name, value);
+/// }
+/// ```
+pub fn named_bits(tag: u32, bits: &[bool]) -> Vec<(&'static str, bool)> {
+    let names: &[&'static str] = match as_request(tag) {
+        x if x == EMS::STATUS as u32 => &EMS_STATUS_BITS,
+        _ => &[],
+    };
+    names.iter().zip(bits.iter()).map(|(name, value)| (*name, *value)).collect()
+}
+
+/// name prefixes of tags which change the state of the device
+const WRITE_PREFIXES: [&str; 12] = [
+    "SET_",
+    "REQ_SET",
+    "LC_SET",
+    "WRITE",
+    "RESET",
+    "DELETE",
+    "CLEAR",
+    "START_",
+    "STOP",
+    "SEND_",
+    "SYSTEM_REBOOT",
+    "RESTART",
+];
+
+/// Returns true if the request tag changes the state of the device, e.g. `SET_*` tags
+///
+/// Write requests must not be resent blindly after a timeout or connection loss, as the device
+/// may have applied them already. The classification is best-effort by the name of the tag, as
+/// the protocol does not mark write tags. Unknown and registered custom tags are treated as write
+/// tags.
+///
+/// # Arguments
+///
+/// * `tag` - the request tag
+///
+/// # Examples
+///
+/// ```
+/// use rscp::tags::{is_write, EMS, SE};
+/// assert!(is_write(SE::SET_EP_RESERVE.into()));
+/// assert!(!is_write(EMS::POWER_PV.into()));
+/// ```
+pub fn is_write(tag: u32) -> bool {
+    let tag = as_request(tag);
+    let group = TagGroup::from((tag >> 24) as u8);
+    let name = group.tags(tag);
+    if group == TagGroup::UNKNOWN || registered(tag).is_some() || name.ends_with(&format!("UNKNOWN({:#010x})", tag)) {
+        return true;
+    }
+    let name = name.strip_prefix(&format!("{:?}_", group)).unwrap_or(&name);
+    WRITE_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+}
+
+/// runtime registry of custom tag names
+static REGISTRY: OnceLock<RwLock<HashMap<u32, String>>> = OnceLock::new();
+
+/// Registers a custom tag name, used if the tag is not known by the built-in tables
+///
+/// # Arguments
+///
+/// * `tag` - the tag id
+/// * `name` - the name of the tag
+///
+/// # Examples
+///
+/// ```
+/// use rscp::tags::{register, TagGroup};
+/// register(0x01fe0001, "EMS_CUSTOM_VALUE");
+/// assert_eq!(TagGroup::EMS.tags(0x01fe0001), "EMS_CUSTOM_VALUE");
+/// ```
+pub fn register(tag: u32, name: &str) {
+    let registry = REGISTRY.get_or_init(|| RwLock::new(HashMap::new()));
+    registry.write().unwrap_or_else(|e| e.into_inner()).insert(tag, name.to_string());
+}
+
+/// Returns the registered name of a custom tag
+fn registered(tag: u32) -> Option<String> {
+    let registry = REGISTRY.get()?;
+    registry.read().unwrap_or_else(|e| e.into_inner()).get(&tag).cloned()
+}
 
 /// This macro is used in the tag group list enum to extend it with the tags function and from u8.
 macro_rules! group_list {
@@ -11,7 +140,8 @@ macro_rules! group_list {
             $($vn = $v),+
         }
 
-        /// Returns the name of the selected enum by id
+        /// Returns the name of the selected enum by id, unknown ids are looked up in the registry of
+        /// custom tags and otherwise returned as `<GROUP>_UNKNOWN(<id>)`
         ///
         /// # Arguments
         ///
@@ -22,12 +152,38 @@ macro_rules! group_list {
         /// ```
         /// use rscp::tags::TagGroup;
         /// let rscp_group = TagGroup::from(0x00);
-        /// println!("{}", rscp_group.tags(0x00000004)); // USER_LEVEL
+        /// println!("{}", rscp_group.tags(0x00000004)); // RSCP_USER_LEVEL
+        /// println!("{}", rscp_group.tags(0x00001234)); // RSCP_UNKNOWN(0x00001234)
         /// ```
         impl $name {
             pub fn tags(&self, id: u32) -> String {
                 match self {
-                    $($name::$vn => $vn::from(id).to_string()),*
+                    $($name::$vn => match $vn::from(id) {
+                        $vn::GENERAL_ERROR if id != $vn::GENERAL_ERROR as u32 => registered(id)
+                            .unwrap_or_else(|| format!(concat!(stringify!($vn), "_UNKNOWN({:#010x})"), id)),
+                        tag => tag.to_string(),
+                    }),*
+                }
+            }
+
+            /// Returns all groups in order of declaration
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use rscp::tags::TagGroup;
+            /// for group in TagGroup::all() {
+            ///     println!("{:?} = {:#04x}", group, group.id());
+            /// }
+            /// ```
+            pub fn all() -> &'static [$name] {
+                &[$($name::$vn),*]
+            }
+
+            /// Returns the byte id of the group, the most significant byte of the tags
+            pub fn id(&self) -> u8 {
+                match self {
+                    $($name::$vn => $v),*
                 }
             }
         }
@@ -3306,10 +3462,55 @@ fn test_tag_group() {
     assert_eq!(RSCP::from(0xffffffffu32), RSCP::GENERAL_ERROR, "Test From Unknown<u32>");
 }
 
+#[test]
+fn test_named_bits() {
+    let bits = named_bits(EMS::STATUS.into(), &[true, false, true, false, true, false, false, true]);
+    assert_eq!(bits, vec![
+        ("CHARGE_LOCKED", true),
+        ("DISCHARGE_LOCKED", false),
+        ("EMERGENCY_POWER_POSSIBLE", true),
+        ("WEATHER_REGULATED_CHARGE_ACTIVE", false),
+        ("POWER_LIMIT_ACTIVE", true),
+        ("CHARGE_LOCK_TIME_ACTIVE", false),
+        ("DISCHARGE_LOCK_TIME_ACTIVE", false),
+    ]);
+    assert_eq!(named_bits(EMS::STATUS.into(), &[true]), vec![("CHARGE_LOCKED", true)]);
+    assert!(named_bits(EMS::POWER_PV.into(), &[true]).is_empty());
+}
+
 #[test]
 fn test_tag_groups() {
     assert_eq!(TagGroup::from(0x00), TagGroup::RSCP, "Test From<u8>");
     assert_eq!(TagGroup::RSCP.tags(0x01u32), "RSCP_AUTHENTICATION", "Test tags(u32)");
+    assert_eq!(TagGroup::RSCP.tags(0x7fffffu32), "RSCP_GENERAL_ERROR", "Test tags(u32) GENERAL_ERROR");
+    assert_eq!(TagGroup::EMS.tags(0x01123456u32), "EMS_UNKNOWN(0x01123456)", "Test tags(u32) unknown");
+    register(0x02123456, "PVI_CUSTOM");
+    assert_eq!(TagGroup::PVI.tags(0x02123456u32), "PVI_CUSTOM", "Test tags(u32) registered");
+    register(0x02000001, "PVI_OVERRIDE");
+    assert_eq!(TagGroup::PVI.tags(0x02000001u32), "PVI_ON_GRID", "Test tags(u32) built-in first");
     // 0xee is currently unused
     assert_eq!(TagGroup::from(0xee), TagGroup::UNKNOWN, "Test From Unknown<u32>");
+    assert_eq!(TagGroup::EMS.id(), 0x01, "Test id()");
+    assert!(TagGroup::all().contains(&TagGroup::EMS), "Test all()");
+    for group in TagGroup::all() {
+        assert_eq!(&TagGroup::from(group.id()), group, "Test all() From<u8> {:?}", group);
+    }
+}
+
+#[test]
+fn test_is_write() {
+    assert!(is_write(EMS::SET_POWER.into()));
+    assert!(is_write(EMS::START_MANUAL_CHARGE.into()));
+    assert!(is_write(OVP::RESET.into()));
+    assert!(is_write(SYS::SYSTEM_REBOOT.into()));
+    assert!(is_write(INFO::SET_TIME.into()));
+    assert!(!is_write(EMS::POWER_PV.into()));
+    assert!(!is_write(EMS::GET_IDLE_PERIODS.into()));
+    assert!(!is_write(INFO::SERIAL_NUMBER as u32 | 0x00800000));
+    assert!(!is_write(DB::HISTORY_TIME_START.into()));
+    assert!(is_write(0x01001234));
+    assert!(is_write(0xf0000001));
+
+    register(0x01fe0002, "EMS_CUSTOM_STATUS");
+    assert!(is_write(0x01fe0002));
 }
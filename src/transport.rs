@@ -0,0 +1,113 @@
+use anyhow::{bail, Result};
+use std::collections::VecDeque;
+
+use crate::{Client, Errors, Frame};
+
+/// Transport exchanging RSCP frames with a device
+pub trait Transport {
+    /// Sends the frame and returns the response frame
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - frame to send
+    fn send_receive_frame(&mut self, frame: &Frame) -> Result<Frame>;
+}
+
+impl Transport for Client {
+    fn send_receive_frame(&mut self, frame: &Frame) -> Result<Frame> {
+        Client::send_receive_frame(self, frame)
+    }
+}
+
+/// Transport without device, records the sent frames and returns canned responses
+///
+/// # Examples
+///
+/// ```
+/// use rscp::{tags, Frame, GetItem, Item, MockClient, Transport};
+/// let mut response = Frame::new();
+/// response.push_item(Item::new(tags::INFO::SERIAL_NUMBER.into(), "serial".to_string()));
+///
+/// let mut mock = MockClient::new();
+/// mock.push_response(response);
+///
+/// let mut request = Frame::new();
+/// request.push_item(Item { tag: tags::INFO::SERIAL_NUMBER.into(), data: None });
+/// let result_frame = mock.send_receive_frame(&request).unwrap();
+/// assert_eq!(result_frame.get_item_data::<String>(tags::INFO::SERIAL_NUMBER.into()).unwrap(), "serial");
+/// assert_eq!(mock.sent_frames().len(), 1);
+/// ```
+pub struct MockClient {
+    /// frames sent to the mock
+    sent: Vec<Frame>,
+
+    /// responses returned in order
+    responses: VecDeque<Frame>,
+}
+
+impl MockClient {
+    /// Returns mock client without responses
+    pub fn new() -> Self {
+        Self { sent: Vec::new(), responses: VecDeque::new() }
+    }
+
+    /// Adds a response returned by one of the following exchanges
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - the response frame
+    pub fn push_response(&mut self, frame: Frame) {
+        self.responses.push_back(frame);
+    }
+
+    /// Returns the frames sent so far
+    pub fn sent_frames(&self) -> &[Frame] {
+        &self.sent
+    }
+}
+
+impl Default for MockClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Transport for MockClient {
+    /// Records the frame and returns the next response, fails with `Errors::ReceiveNothing` if there is none
+    fn send_receive_frame(&mut self, frame: &Frame) -> Result<Frame> {
+        self.sent.push(frame.clone());
+        match self.responses.pop_front() {
+            Some(response) => Ok(response),
+            None => bail!(Errors::ReceiveNothing),
+        }
+    }
+}
+
+/// ################################################
+///      TEST TEST TEST
+/// ################################################
+
+#[test]
+fn test_mock_client() {
+    use crate::{tags, GetItem, Item};
+
+    /// application logic driving any transport
+    fn read_serial<T: Transport>(transport: &mut T) -> Result<String> {
+        let mut request = Frame::new();
+        request.push_item(Item::from_tag(tags::INFO::SERIAL_NUMBER));
+        let response = transport.send_receive_frame(&request)?;
+        Ok(response.get_item_data::<String>(tags::INFO::SERIAL_NUMBER.into())?.clone())
+    }
+
+    let mut response = Frame::new();
+    response.push_item(Item::new_tag(tags::INFO::SERIAL_NUMBER, "serial".to_string()));
+    let mut mock = MockClient::new();
+    mock.push_response(response);
+
+    assert_eq!(read_serial(&mut mock).unwrap(), "serial");
+    assert_eq!(mock.sent_frames().len(), 1);
+    assert!(mock.sent_frames()[0].get_item(tags::INFO::SERIAL_NUMBER.into()).is_ok());
+
+    assert_eq!(format!("{}", read_serial(&mut mock).unwrap_err()), "Receive nothing");
+    assert_eq!(mock.sent_frames().len(), 2);
+}
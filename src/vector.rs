@@ -0,0 +1,194 @@
+use anyhow::{anyhow, bail, Result};
+
+use crate::{Errors, Frame};
+
+/// Captured frame with the expected parse result, used as regression fixture
+///
+/// Test vectors are stored in a line based text format, each vector starts with the name in
+/// brackets followed by `key = value` lines. Empty lines and lines starting with `#` are ignored.
+///
+/// ```text
+/// [auth response]
+/// parses = true
+/// checksum_ok = true
+/// bytes = e3dc0011...
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct TestVector {
+    /// name of the test vector
+    pub name: String,
+
+    /// the serialized frame
+    pub bytes: Vec<u8>,
+
+    /// true if the frame is expected to be parsed by `Frame::from_bytes`
+    pub parses: bool,
+
+    /// true if the checksum is expected to match, see `Frame::from_bytes_lenient`
+    pub checksum_ok: bool,
+}
+
+impl TestVector {
+    /// Returns test vector of the serialized frame
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - name of the test vector
+    /// * `frame` - the frame
+    pub fn from_frame(name: &str, frame: &Frame) -> Result<Self> {
+        Ok(Self::from_bytes(name, frame.to_bytes()?))
+    }
+
+    /// Returns test vector of the captured data, the expectations are taken from the current parser
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - name of the test vector
+    /// * `bytes` - the captured frame data
+    pub fn from_bytes(name: &str, bytes: Vec<u8>) -> Self {
+        Self {
+            name: name.to_string(),
+            parses: Frame::from_bytes(bytes.clone()).is_ok(),
+            checksum_ok: matches!(Frame::from_bytes_lenient(bytes.clone()), Ok((_, true))),
+            bytes,
+        }
+    }
+
+    /// Returns the test vector in the text format
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rscp::{Frame, TestVector};
+    /// let vector = TestVector::from_frame("empty", &Frame::new()).unwrap();
+    /// let vectors = TestVector::parse_all(&vector.to_text()).unwrap();
+    /// assert_eq!(vectors, vec![vector]);
+    /// ```
+    pub fn to_text(&self) -> String {
+        let hex: String = self.bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+        format!("[{}]\nparses = {}\nchecksum_ok = {}\nbytes = {}\n", self.name, self.parses, self.checksum_ok, hex)
+    }
+
+    /// Returns the test vectors of the text
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - test vectors in the text format
+    pub fn parse_all(text: &str) -> Result<Vec<Self>> {
+        let mut vectors: Vec<Self> = Vec::new();
+        for (index, line) in text.lines().enumerate().map(|(index, line)| (index + 1, line.trim())) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+                vectors.push(Self { name: name.to_string(), bytes: Vec::new(), parses: true, checksum_ok: true });
+                continue;
+            }
+            let vector = vectors.last_mut().ok_or_else(|| invalid_line(index, "value before the name"))?;
+            let (key, value) = line.split_once('=').ok_or_else(|| invalid_line(index, "missing ="))?;
+            match key.trim() {
+                "parses" => vector.parses = value.trim().parse().map_err(|_| invalid_line(index, "invalid bool"))?,
+                "checksum_ok" => vector.checksum_ok = value.trim().parse().map_err(|_| invalid_line(index, "invalid bool"))?,
+                "bytes" => vector.bytes = parse_hex(value.trim()).ok_or_else(|| invalid_line(index, "invalid hex"))?,
+                key => return Err(invalid_line(index, &format!("unknown key {}", key))),
+            }
+        }
+        Ok(vectors)
+    }
+
+    /// Checks the parser against the expectations, frames which are expected to be parsed must
+    /// serialize to bytes which parse to the identical frame again
+    ///
+    /// The serialized frame can differ from the captured bytes, as the parser drops the response
+    /// bit of the tags and the zero padding.
+    pub fn verify(&self) -> Result<()> {
+        match Frame::from_bytes(self.bytes.clone()) {
+            Ok(frame) if self.parses => {
+                let bytes = frame.to_bytes()?;
+                if Frame::from_bytes(bytes.clone())?.to_bytes()? != bytes {
+                    bail!("Test vector {}: serialized frame differs", self.name)
+                }
+            }
+            Ok(_) => bail!("Test vector {}: frame parsed unexpectedly", self.name),
+            Err(err) if self.parses => bail!("Test vector {}: {}", self.name, err),
+            Err(_) => (),
+        }
+        let checksum_ok = matches!(Frame::from_bytes_lenient(self.bytes.clone()), Ok((_, true)));
+        if checksum_ok != self.checksum_ok {
+            bail!("Test vector {}: checksum_ok is {}", self.name, checksum_ok)
+        }
+        Ok(())
+    }
+}
+
+/// Returns parse error of the test vector line
+///
+/// # Arguments
+///
+/// * `index` - the line number
+/// * `msg` - the error description
+fn invalid_line(index: usize, msg: &str) -> anyhow::Error {
+    anyhow!(Errors::Parse(format!("Invalid test vector line {}: {}", index, msg)))
+}
+
+/// Returns the bytes of the hex string, `None` on invalid digits or odd length
+///
+/// # Arguments
+///
+/// * `hex` - the hex string
+fn parse_hex(hex: &str) -> Option<Vec<u8>> {
+    hex.as_bytes()
+        .chunks(2)
+        .map(|digits| std::str::from_utf8(digits).ok().filter(|digits| digits.len() == 2).and_then(|digits| u8::from_str_radix(digits, 16).ok()))
+        .collect()
+}
+
+/// ################################################
+///      TEST TEST TEST
+/// ################################################
+
+#[test]
+fn test_vector_round_trip() {
+    use crate::{tags, Item};
+
+    let text = "# captured authentication response\n\
+        [auth response]\n\
+        parses = true\n\
+        checksum_ok = true\n\
+        bytes = e3dc00114e61bc000000000040e201000700010000 0a000000fefa843c\n";
+    assert!(TestVector::parse_all(text).is_err());
+
+    let text = text.replace("0000 0a", "00000a");
+    let vectors = TestVector::parse_all(&text).unwrap();
+    assert_eq!(vectors.len(), 1);
+    let vector = &vectors[0];
+    assert_eq!(vector.name, "auth response");
+    vector.verify().unwrap();
+    let frame = Frame::from_bytes(vector.bytes.clone()).unwrap();
+    assert_eq!(frame.to_bytes().unwrap(), vector.bytes);
+    assert_eq!(TestVector::parse_all(&vector.to_text()).unwrap(), vectors);
+
+    let mut frame = Frame::new();
+    frame.push_item(Item::new_tag(tags::EMS::POWER_PV, 1200i32));
+    let mut corrupt = TestVector::from_frame("corrupt checksum", &frame).unwrap();
+    let last = corrupt.bytes.len() - 1;
+    corrupt.bytes[last] ^= 0xff;
+    let corrupt = TestVector::from_bytes(&corrupt.name, corrupt.bytes);
+    assert!(!corrupt.parses);
+    assert!(!corrupt.checksum_ok);
+    corrupt.verify().unwrap();
+
+    let mut wrong = corrupt.clone();
+    wrong.parses = true;
+    assert!(wrong.verify().is_err());
+
+    let text = format!("{}\n{}", TestVector::from_frame("power", &frame).unwrap().to_text(), corrupt.to_text());
+    let vectors = TestVector::parse_all(&text).unwrap();
+    assert_eq!(vectors.len(), 2);
+    for vector in vectors {
+        vector.verify().unwrap();
+    }
+
+    assert_eq!(TestVector::parse_all("parses = true").unwrap_err().to_string(), "Frame parse error: Invalid test vector line 1: value before the name");
+    assert_eq!(TestVector::parse_all("[x]\nfoo = 1").unwrap_err().to_string(), "Frame parse error: Invalid test vector line 2: unknown key foo");
+}
@@ -0,0 +1,100 @@
+use chrono::Weekday;
+
+/// Set of week days of schedules, encoded as bitmask with monday as least significant bit
+#[derive(Copy, Clone, Default, PartialEq, Eq, Debug)]
+pub struct Weekdays(u8);
+
+impl Weekdays {
+    /// bitmask of all seven days
+    const ALL_BITS: u8 = 0x7f;
+
+    /// Returns the set of the days in the bitmask, bits above sunday are ignored
+    ///
+    /// # Arguments
+    ///
+    /// * `bits` - bitmask with monday as least significant bit
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::Weekday;
+    /// use rscp::Weekdays;
+    /// let weekdays = Weekdays::from_bits(0b0100_0001);
+    /// assert!(weekdays.contains(Weekday::Mon));
+    /// assert!(weekdays.contains(Weekday::Sun));
+    /// assert!(!weekdays.contains(Weekday::Tue));
+    /// ```
+    pub fn from_bits(bits: u8) -> Self {
+        Self(bits & Self::ALL_BITS)
+    }
+
+    /// Returns the bitmask of the days
+    pub fn to_bits(&self) -> u8 {
+        self.0
+    }
+
+    /// Returns true if the day is in the set
+    ///
+    /// # Arguments
+    ///
+    /// * `day` - the week day
+    pub fn contains(&self, day: Weekday) -> bool {
+        self.0 & Self::bit(day) != 0
+    }
+
+    /// Adds the day to the set
+    ///
+    /// # Arguments
+    ///
+    /// * `day` - the week day
+    pub fn insert(&mut self, day: Weekday) {
+        self.0 |= Self::bit(day);
+    }
+
+    /// Returns the days of the set starting with monday
+    pub fn days(&self) -> Vec<Weekday> {
+        let mut day = Weekday::Mon;
+        let mut days = Vec::new();
+        for _ in 0..7 {
+            if self.contains(day) {
+                days.push(day);
+            }
+            day = day.succ();
+        }
+        days
+    }
+
+    /// Returns the bit of the day
+    fn bit(day: Weekday) -> u8 {
+        1 << day.num_days_from_monday()
+    }
+}
+
+impl FromIterator<Weekday> for Weekdays {
+    fn from_iter<I: IntoIterator<Item = Weekday>>(iter: I) -> Self {
+        let mut weekdays = Self::default();
+        for day in iter {
+            weekdays.insert(day);
+        }
+        weekdays
+    }
+}
+
+/// ################################################
+///      TEST TEST TEST
+/// ################################################
+
+#[test]
+fn test_weekdays() {
+    let weekdays = Weekdays::from_bits(0b0001_1111);
+    assert_eq!(weekdays.days(), vec![Weekday::Mon, Weekday::Tue, Weekday::Wed, Weekday::Thu, Weekday::Fri]);
+    assert!(!weekdays.contains(Weekday::Sat));
+    assert_eq!(weekdays.to_bits(), 0x1f);
+
+    let weekend: Weekdays = [Weekday::Sat, Weekday::Sun].into_iter().collect();
+    assert_eq!(weekend.to_bits(), 0b0110_0000);
+    assert_eq!(Weekdays::from_bits(weekend.to_bits()), weekend);
+
+    assert_eq!(Weekdays::from_bits(0xff).to_bits(), 0x7f);
+    assert!(Weekdays::default().days().is_empty());
+}